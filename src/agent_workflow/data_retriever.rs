@@ -1,11 +1,16 @@
 use async_trait::async_trait;
 
 use task_graph::{Context, ContextExt, GraphError, Task};
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 
+use crate::agent_workflow::IngestedDocumentStore;
+use crate::agent_workflow::OrganicResult;
 use crate::agent_workflow::ScraperSingleton;
-use crate::agent_workflow::SearchResponse;
 use crate::agent_workflow::context_vars;
+use crate::agent_workflow::search_engine::{EngineErrorInfo, aggregate_search, build_engines};
+use crate::agent_workflow::similarity_filter::SimilarityFilter;
+use crate::cache::{CacheExt, cache_key, record_cache_access, shared_cache};
+use crate::config::Config;
 
 #[derive(Debug, Clone)]
 pub struct DataRetrieverTask;
@@ -44,6 +49,36 @@ fn is_scrapeable_url(url: &str) -> bool {
 #[async_trait]
 impl Task for DataRetrieverTask {
     async fn run(&self, context: Context) -> Result<(), GraphError> {
+        let document_ids: Option<Vec<String>> =
+            context.get(context_vars::INGESTED_DOCUMENT_IDS).await;
+
+        if let Some(document_ids) = document_ids.filter(|ids| !ids.is_empty()) {
+            info!(
+                "Bypassing web search, using {} ingested document(s)",
+                document_ids.len()
+            );
+
+            let search_results: Vec<String> = document_ids
+                .iter()
+                .filter_map(|id| {
+                    let content = IngestedDocumentStore::get(id);
+                    if content.is_none() {
+                        warn!("Ingested document id '{}' not found, skipping", id);
+                    }
+                    content
+                })
+                .collect();
+
+            context
+                .set(context_vars::ENGINE_ERRORS, Vec::<EngineErrorInfo>::new())
+                .await;
+            context
+                .set(context_vars::SEARCH_RESULTS, search_results)
+                .await;
+
+            return Ok(());
+        }
+
         info!("Retrieving data");
         let query: String = context
             .get(context_vars::ENHANCED_QUERY)
@@ -52,93 +87,169 @@ impl Task for DataRetrieverTask {
 
         info!("Data retriever using enhanced query: '[{}]'", query);
 
-        let search_response = retrieve_data(query).await.map_err(|e| {
+        let (organic_results, engine_errors) = retrieve_data(query).await.map_err(|e| {
             GraphError::TaskExecutionFailed(format!("Failed to retrieve data: {}", e))
         })?;
 
-        info!("Retrieved {} search results", search_response.organic.len());
-
-        // Filter URLs to only include scrapeable ones
-        let scrapeable_results: Vec<_> = search_response
-            .organic
-            .iter()
-            .filter(|result| {
-                let is_scrapeable = is_scrapeable_url(&result.link);
-                if !is_scrapeable {
-                    warn!(
-                        "Skipping non-scrapeable URL: {} ({})",
-                        result.link, result.title
-                    );
-                }
-                is_scrapeable
-            })
-            .collect();
-
-        info!("Filtered to {} scrapeable URLs", scrapeable_results.len());
-
-        if scrapeable_results.is_empty() {
-            warn!("No scrapeable URLs found in search results");
-            context
-                .set(context_vars::SEARCH_RESULTS, Vec::<String>::new())
-                .await;
-            return Ok(());
+        if !engine_errors.is_empty() {
+            warn!(
+                "{} search engine(s) failed: {:?}",
+                engine_errors.len(),
+                engine_errors
+            );
         }
+        context
+            .set(context_vars::ENGINE_ERRORS, engine_errors)
+            .await;
 
-        let scraper = ScraperSingleton::get().map_err(|e| {
-            GraphError::TaskExecutionFailed(format!("Failed to get scraper: {}", e))
-        })?;
-
-        let scrape_futures = scrapeable_results.iter().map(|result| {
-            info!("Scraping URL: {}", result.link);
-            scraper.scrape_text(result.link.as_str())
-        });
+        info!("Retrieved {} search results", organic_results.len());
 
-        let scraped_results: Vec<String> = futures::future::join_all(scrape_futures)
+        let search_results = scrape_and_filter(&organic_results)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
             .map_err(|e| GraphError::TaskExecutionFailed(format!("Failed to scrape: {}", e)))?;
 
-        // Filter out empty or very short scraped content
-        let valid_scraped_txts: Vec<String> = scraped_results
-            .into_iter()
-            .filter(|text| text.trim().len() > 100) // Only keep substantial content
-            .collect();
-
-        info!(
-            "Successfully scraped {} URLs with substantial content",
-            valid_scraped_txts.len()
-        );
-
         context
-            .set(context_vars::SEARCH_RESULTS, valid_scraped_txts)
+            .set(context_vars::SEARCH_RESULTS, search_results)
             .await;
 
         Ok(())
     }
 }
 
-const BASE_URL: &str = "https://google.serper.dev/search";
-const SEARCH_TARGET: &str = "site:www.btselem.org";
-
-async fn retrieve_data(query: String) -> anyhow::Result<SearchResponse> {
-    let api_key =
-        std::env::var("SERPER_API_KEY").map_err(|_| anyhow::anyhow!("SERPER_API_KEY not set"))?;
-    let client = reqwest::Client::builder().build()?;
-    let query_encoded = query.split_whitespace().collect::<Vec<_>>().join("+");
-    let url = format!(
-        "{}?q={}+{}&apiKey={}&num=5&tbs=qdr:3y",
-        BASE_URL, query_encoded, SEARCH_TARGET, api_key
+/// Filter `results` down to scrapeable URLs, scrape each concurrently via the
+/// singleton [`WebScraper`](crate::scraper::WebScraper), drop empty/too-short
+/// pages, and optionally collapse near-duplicates via [`SimilarityFilter`]
+/// when `Config::dedupe_similar_pages` is set.
+///
+/// Shared by [`DataRetrieverTask`] and
+/// [`MultiQueryExpandTask`](crate::agent_workflow::multi_query_expand::MultiQueryExpandTask)
+/// so both end up populating `SEARCH_RESULTS` the same way.
+pub(crate) async fn scrape_and_filter(results: &[OrganicResult]) -> anyhow::Result<Vec<String>> {
+    let scrapeable_results: Vec<_> = results
+        .iter()
+        .filter(|result| {
+            let is_scrapeable = is_scrapeable_url(&result.link);
+            crate::metrics::record_url_filtered(is_scrapeable);
+            if !is_scrapeable {
+                warn!(
+                    "Skipping non-scrapeable URL: {} ({})",
+                    result.link, result.title
+                );
+            }
+            is_scrapeable
+        })
+        .collect();
+
+    info!("Filtered to {} scrapeable URLs", scrapeable_results.len());
+
+    if scrapeable_results.is_empty() {
+        warn!("No scrapeable URLs found in search results");
+        return Ok(Vec::new());
+    }
+
+    let scraper = ScraperSingleton::get()?;
+
+    let scrape_futures = scrapeable_results.iter().map(|result| {
+        info!("Scraping URL: {}", result.link);
+        let started_at = std::time::Instant::now();
+        let scrape = scraper.scrape_text(result.link.as_str());
+        async move {
+            let outcome = scrape.await;
+            crate::metrics::record_scrape_latency(started_at.elapsed().as_secs_f64());
+            outcome
+        }
+    });
+
+    let scraped_results: Vec<String> = futures::future::join_all(scrape_futures)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Filter out empty or very short scraped content
+    let valid_scraped_txts: Vec<String> = scraped_results
+        .into_iter()
+        .filter(|text| {
+            let keep = text.trim().len() > 100; // Only keep substantial content
+            if !keep {
+                crate::metrics::record_page_dropped();
+            }
+            keep
+        })
+        .collect();
+
+    info!(
+        "Successfully scraped {} URLs with substantial content",
+        valid_scraped_txts.len()
     );
-    info!("Executing search with URL: {}", url);
-    let request = client.request(reqwest::Method::GET, &url);
-    let response = request.send().await?;
-    info!("Received response status: {}", response.status());
-    let body = response.text().await?;
-    debug!("Response body: {}", body);
-
-    let search_response: SearchResponse = serde_json::from_str(&body)?;
-    Ok(search_response)
+
+    let config = Config::from_env();
+    let deduped_txts = if config.dedupe_similar_pages {
+        let mut filter = SimilarityFilter::new(config.near_duplicate_threshold);
+        let before = valid_scraped_txts.len();
+        let kept: Vec<String> = valid_scraped_txts
+            .into_iter()
+            .filter(|text| filter.should_keep(text))
+            .collect();
+        if kept.len() < before {
+            info!(
+                "Dropped {} near-duplicate page(s), {} remaining",
+                before - kept.len(),
+                kept.len()
+            );
+        }
+        kept
+    } else {
+        valid_scraped_txts
+    };
+
+    Ok(deduped_txts)
+}
+
+/// Query every configured [`SearchEngine`](crate::agent_workflow::search_engine::SearchEngine)
+/// concurrently, aggregate and deduplicate their results, and return the
+/// merged list alongside any per-engine failures.
+///
+/// The aggregated result is cached under a key derived from the enhanced
+/// query so repeat questions skip the full multi-engine fan-out.
+async fn retrieve_data(
+    query: String,
+) -> anyhow::Result<(Vec<OrganicResult>, Vec<EngineErrorInfo>)> {
+    let config = Config::from_env();
+    let cache = shared_cache(config.redis_url.as_deref());
+    let key = cache_key("search", &query);
+
+    if let Some(cached) = cache
+        .get::<(Vec<OrganicResult>, Vec<EngineErrorInfo>)>(&key)
+        .await
+        .unwrap_or(None)
+    {
+        record_cache_access("search", true);
+        info!("Search cache hit for query '[{}]'", query);
+        return Ok(cached);
+    }
+    record_cache_access("search", false);
+
+    let engines = build_engines(&config);
+    if engines.is_empty() {
+        return Err(anyhow::anyhow!("No search engines configured or available"));
+    }
+
+    let started_at = std::time::Instant::now();
+    let result = aggregate_search(&engines, &query).await?;
+    crate::metrics::record_search_latency(started_at.elapsed().as_secs_f64());
+
+    if let Err(e) = cache
+        .set(
+            &key,
+            &result,
+            std::time::Duration::from_secs(config.cache_ttl_seconds),
+        )
+        .await
+    {
+        warn!("Failed to cache search results for '[{}]': {}", query, e);
+    }
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -184,28 +295,20 @@ mod tests {
         let query = "human rights violations".to_string();
         let result = retrieve_data(query).await;
 
-        assert!(result.is_ok(), "API call should succeed");
+        assert!(result.is_ok(), "Aggregated search should succeed");
 
-        let search_response = result.unwrap();
+        let (organic_results, _engine_errors) = result.unwrap();
         assert!(
-            !search_response.organic.is_empty(),
+            !organic_results.is_empty(),
             "Response should contain organic results"
         );
-        assert!(
-            !search_response.search_parameters.q.is_empty(),
-            "Search parameters should contain query"
-        );
 
-        println!("Retrieved {} search results", search_response.organic.len());
-        println!("Search query: {}", search_response.search_parameters.q);
+        println!("Retrieved {} search results", organic_results.len());
 
-        if !search_response.organic.is_empty() {
-            println!("First result title: {}", search_response.organic[0].title);
-            println!("First result link: {}", search_response.organic[0].link);
-            println!(
-                "First result snippet: {}",
-                search_response.organic[0].snippet
-            );
+        if let Some(first) = organic_results.first() {
+            println!("First result title: {}", first.title);
+            println!("First result link: {}", first.link);
+            println!("First result snippet: {}", first.snippet);
         }
     }
 
@@ -231,7 +334,7 @@ mod tests {
         );
 
         // Verify the search results were stored in context
-        let search_results: Option<Vec<crate::agent_workflow::OrganicResult>> =
+        let search_results: Option<Vec<String>> =
             context.get(context_vars::SEARCH_RESULTS).await;
         assert!(
             search_results.is_some(),
@@ -281,57 +384,35 @@ mod tests {
 
         assert!(
             result.is_ok(),
-            "API call with multiple keywords should succeed"
+            "Aggregated search with multiple keywords should succeed"
         );
 
-        let search_response = result.unwrap();
-        assert!(
-            !search_response.organic.is_empty(),
-            "Response should contain results"
-        );
+        let (organic_results, _engine_errors) = result.unwrap();
+        assert!(!organic_results.is_empty(), "Response should contain results");
 
         println!(
             "Retrieved {} results for multi-keyword query",
-            search_response.organic.len()
+            organic_results.len()
         );
 
-        // Print details about each result
-        for (index, result) in search_response.organic.iter().enumerate() {
+        for (index, result) in organic_results.iter().enumerate() {
             println!("Result {}: {}", index + 1, result.title);
             println!("  Link: {}", result.link);
             println!("  Position: {}", result.position);
-            if let Some(date) = &result.date {
-                println!("  Date: {}", date);
-            }
         }
     }
 
     #[tokio::test]
-    async fn test_search_response_structure() {
+    async fn test_organic_result_structure() {
         let query = "katz gaza starvation".to_string();
         let result = retrieve_data(query).await;
 
-        assert!(result.is_ok(), "API call should succeed");
-
-        let search_response = result.unwrap();
-
-        // Test search parameters structure
-        assert_eq!(search_response.search_parameters.engine, "google");
-        assert_eq!(search_response.search_parameters.search_type, "search");
-        assert!(
-            search_response
-                .search_parameters
-                .q
-                .contains("site:www.btselem.org")
-        );
+        assert!(result.is_ok(), "Aggregated search should succeed");
 
-        // Test organic results structure
-        assert!(
-            !search_response.organic.is_empty(),
-            "Should have organic results"
-        );
+        let (organic_results, _engine_errors) = result.unwrap();
+        assert!(!organic_results.is_empty(), "Should have organic results");
 
-        for result in &search_response.organic {
+        for result in &organic_results {
             assert!(!result.title.is_empty(), "Result should have a title");
             assert!(!result.link.is_empty(), "Result should have a link");
             assert!(
@@ -339,15 +420,13 @@ mod tests {
                 "Link should be from btselem.org"
             );
             assert!(!result.snippet.is_empty(), "Result should have a snippet");
-            assert!(result.position > 0, "Position should be greater than 0");
         }
 
-        println!("Search response structure is valid");
-        println!("Total results: {}", search_response.organic.len());
-        println!("Query: {}", search_response.search_parameters.q);
+        println!("Search results structure is valid");
+        println!("Total results: {}", organic_results.len());
 
         // Show summary of results
-        for (i, result) in search_response.organic.iter().enumerate().take(3) {
+        for (i, result) in organic_results.iter().enumerate().take(3) {
             println!(
                 "{}. {} (Position: {})",
                 i + 1,
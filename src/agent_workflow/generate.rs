@@ -1,10 +1,13 @@
 use crate::agent_workflow::OrganicResult;
 use crate::agent_workflow::context_vars;
 use crate::agent_workflow::get_llm_agent;
+use crate::agent_workflow::search_engine::EngineErrorInfo;
+use crate::cache::{CacheExt, cache_key, record_cache_access, shared_cache};
+use crate::config::Config;
 use async_trait::async_trait;
 use rig::completion::Prompt;
 use task_graph::{Context, ContextExt, GraphError, Task};
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone)]
 pub struct GenerateAnswerTask;
@@ -23,17 +26,43 @@ impl Task for GenerateAnswerTask {
             .await
             .ok_or_else(|| GraphError::TaskExecutionFailed("Missing question".to_string()))?;
 
+        let engine_errors: Vec<EngineErrorInfo> = context
+            .get(context_vars::ENGINE_ERRORS)
+            .await
+            .unwrap_or_default();
+
         let answer = generate_answer(question, search_results)
             .await
             .map_err(|e| {
                 GraphError::TaskExecutionFailed(format!("Failed to generate answer: {}", e))
             })?;
+        let answer = append_engine_error_note(answer, &engine_errors);
         //info!("Answer: {}", answer);
         context.set(context_vars::ANSWER, answer).await;
         Ok(())
     }
 }
 
+/// Append a short note listing any search engines that failed during
+/// retrieval, so an answer built from a partial result set says so instead
+/// of silently looking complete.
+fn append_engine_error_note(answer: String, engine_errors: &[EngineErrorInfo]) -> String {
+    if engine_errors.is_empty() {
+        return answer;
+    }
+
+    let engines = engine_errors
+        .iter()
+        .map(|e| format!("{} ({})", e.engine, e.message))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{}\n\nNote: the following search engine(s) failed and may be missing from these results: {}",
+        answer, engines
+    )
+}
+
 const PROMPT: &str = r#"
 Question: {question}
 
@@ -49,11 +78,40 @@ Your task is to answer the question based on the texts only. Do not make up any
 note the dates in the texts to make sure you are using the most recent information.
 "#;
 
+/// Generate an answer from the question and scraped texts, caching the
+/// result under a key derived from both so a repeated question only hits the
+/// LLM again if the underlying search results have changed.
 async fn generate_answer(question: String, search_results: Vec<String>) -> anyhow::Result<String> {
+    let config = Config::from_env();
+    let cache = shared_cache(config.redis_url.as_deref());
+    let key = cache_key(
+        "answer",
+        &format!("{}\n{}", question, search_results.join("\n")),
+    );
+
+    if let Some(cached) = cache.get::<String>(&key).await.unwrap_or(None) {
+        record_cache_access("answer", true);
+        info!("Answer cache hit for question '[{}]'", question);
+        return Ok(cached);
+    }
+    record_cache_access("answer", false);
+
     let agent = get_llm_agent(SYSTEM_PROMPT)?;
     let prompt = PROMPT
         .replace("{question}", &question)
         .replace("{search_results}", &search_results.join("\n"));
     let response = agent.prompt(prompt).await?;
+
+    if let Err(e) = cache
+        .set(
+            &key,
+            &response,
+            std::time::Duration::from_secs(config.cache_ttl_seconds),
+        )
+        .await
+    {
+        warn!("Failed to cache generated answer for '[{}]': {}", question, e);
+    }
+
     Ok(response)
 }
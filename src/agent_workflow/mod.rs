@@ -1,8 +1,14 @@
 pub mod data_retriever;
 pub mod generate;
+pub mod multi_query_expand;
 pub mod query_enhancer;
+pub mod search_engine;
+pub mod similarity_filter;
 use crate::{agent_workflow::data_retriever::DataRetrieverTask, scraper::WebScraper};
+use crate::config::Config;
+use dashmap::DashMap;
 use generate::GenerateAnswerTask;
+use multi_query_expand::MultiQueryExpandTask;
 use once_cell::sync::OnceCell;
 use query_enhancer::QueryEnhanceTask;
 use rig::{agent::Agent, providers::openrouter};
@@ -13,6 +19,35 @@ use task_graph::TaskGraph;
 // Singleton WebScraper instance that can be shared across tasks
 static SCRAPER_INSTANCE: OnceCell<Arc<WebScraper>> = OnceCell::new();
 
+// Documents uploaded via the GraphQL `uploadDocument` mutation, keyed by
+// upload id, so `DataRetrieverTask` can pull them in without going through
+// `ScraperSingleton`/a search engine.
+static INGESTED_DOCUMENTS: OnceCell<Arc<DashMap<String, String>>> = OnceCell::new();
+
+/// Store for ad-hoc documents ingested via GraphQL, consulted by
+/// [`DataRetrieverTask`](crate::agent_workflow::data_retriever::DataRetrieverTask)
+/// when a query names ingested document ids to use as its retrieval source
+/// instead of a live web search.
+pub struct IngestedDocumentStore;
+
+impl IngestedDocumentStore {
+    fn instance() -> &'static Arc<DashMap<String, String>> {
+        INGESTED_DOCUMENTS.get_or_init(|| Arc::new(DashMap::new()))
+    }
+
+    /// Store `content` under a fresh id and return it.
+    pub fn insert(content: String) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        Self::instance().insert(id.clone(), content);
+        id
+    }
+
+    /// Fetch a previously ingested document's content by id, if it exists.
+    pub fn get(id: &str) -> Option<String> {
+        Self::instance().get(id).map(|entry| entry.clone())
+    }
+}
+
 pub struct ScraperSingleton;
 
 impl ScraperSingleton {
@@ -45,15 +80,40 @@ pub mod context_vars {
     pub const ENHANCED_QUERY: &str = "enhanced_query";
     pub const ANSWER: &str = "answer";
     pub const SEARCH_RESULTS: &str = "search_results";
+    /// Per-engine failures from the search fan-out, stored alongside
+    /// `SEARCH_RESULTS` so the answer can acknowledge partial coverage.
+    pub const ENGINE_ERRORS: &str = "engine_errors";
+    /// The K diverse query reformulations generated by `MultiQueryExpandTask`,
+    /// stored in place of a single `ENHANCED_QUERY` (which that task does not
+    /// set) for diagnostics/debugging.
+    pub const QUERY_VARIANTS: &str = "query_variants";
+    /// Ids of documents uploaded via the GraphQL `uploadDocument` mutation
+    /// (see `IngestedDocumentStore`), set before graph execution when a
+    /// query should use them as its retrieval source instead of a live web
+    /// search.
+    pub const INGESTED_DOCUMENT_IDS: &str = "ingested_document_ids";
 }
 
+/// Build the agent graph for `query`. When
+/// [`Config::use_multi_query_expansion`] is set, `MultiQueryExpandTask`
+/// replaces the `QueryEnhanceTask` + `DataRetrieverTask` pair - it expands
+/// the query into several reformulations, fuses their ranked results with
+/// Reciprocal Rank Fusion, and scrapes the fused set itself, so it feeds
+/// `GenerateAnswerTask` directly.
 pub fn create_agent_workflow(query: String) -> anyhow::Result<TaskGraph> {
+    let config = Config::from_env();
     let mut graph = TaskGraph::new();
-    let enhance_task = QueryEnhanceTask::new(query);
     let generate_task = GenerateAnswerTask;
-    let retriever_task = DataRetrieverTask;
-    graph.add_edge(enhance_task, retriever_task.clone())?;
-    graph.add_edge(retriever_task, generate_task)?;
+
+    if config.use_multi_query_expansion {
+        let expand_task = MultiQueryExpandTask::new(query);
+        graph.add_edge(expand_task, generate_task)?;
+    } else {
+        let enhance_task = QueryEnhanceTask::new(query);
+        let retriever_task = DataRetrieverTask;
+        graph.add_edge(enhance_task, retriever_task.clone())?;
+        graph.add_edge(retriever_task, generate_task)?;
+    }
     Ok(graph)
 }
 
@@ -105,4 +165,11 @@ pub struct OrganicResult {
     pub snippet: String, // Maps htmlSnippet to snippet for consistency
     #[serde(rename = "displayLink")]
     pub display_link: Option<String>,
+    /// Rank within its source engine's result list (lower is better); used
+    /// by the multi-engine aggregator to re-rank merged results.
+    #[serde(default)]
+    pub position: usize,
+    /// Names of the search engines that surfaced this URL.
+    #[serde(default)]
+    pub engines: Vec<String>,
 }
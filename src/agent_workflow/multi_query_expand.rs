@@ -0,0 +1,222 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use task_graph::{Context, ContextExt, GraphError, Task};
+use tracing::{info, warn};
+
+use crate::agent_workflow::context_vars;
+use crate::agent_workflow::data_retriever::{scrape_and_filter, DataRetrieverTask};
+use crate::agent_workflow::get_llm_agent;
+use crate::agent_workflow::search_engine::{aggregate_search, build_engines, EngineErrorInfo};
+use crate::agent_workflow::OrganicResult;
+use crate::config::Config;
+
+use rig::completion::Prompt;
+
+/// Number of diverse query reformulations requested from the LLM.
+const NUM_QUERY_VARIANTS: usize = 4;
+
+/// RRF's rank-smoothing constant; a larger `k` flattens the influence of
+/// rank differences, a smaller `k` rewards top ranks more steeply. 60 is the
+/// value originally proposed by Cormack et al. and is a common default.
+const RRF_K: f64 = 60.0;
+
+/// Number of fused results kept after Reciprocal Rank Fusion.
+const TOP_M_FUSED_RESULTS: usize = 10;
+
+/// Expands a user query into [`NUM_QUERY_VARIANTS`] diverse reformulations,
+/// retrieves each independently, and fuses the ranked result lists with
+/// Reciprocal Rank Fusion before scraping. A higher-recall alternative to
+/// [`QueryEnhanceTask`](crate::agent_workflow::query_enhancer::QueryEnhanceTask)
+/// + [`DataRetrieverTask`](crate::agent_workflow::data_retriever::DataRetrieverTask):
+/// [`create_agent_workflow`](crate::agent_workflow::create_agent_workflow)
+/// uses it in their place when `Config::use_multi_query_expansion` is set.
+/// It still populates `SEARCH_RESULTS` the same way, so
+/// [`GenerateAnswerTask`](crate::agent_workflow::generate::GenerateAnswerTask)
+/// is unaffected.
+#[derive(Debug, Clone)]
+pub struct MultiQueryExpandTask {
+    query: String,
+}
+
+impl MultiQueryExpandTask {
+    pub fn new(query: String) -> Self {
+        Self { query }
+    }
+}
+
+#[async_trait]
+impl Task for MultiQueryExpandTask {
+    async fn run(&self, context: Context) -> Result<(), GraphError> {
+        context.set(context_vars::QUERY, self.query.clone()).await;
+
+        // Uploaded documents replace the web search entirely, same as in the
+        // default pipeline, so hand off to `DataRetrieverTask`'s bypass.
+        let document_ids: Option<Vec<String>> =
+            context.get(context_vars::INGESTED_DOCUMENT_IDS).await;
+        if document_ids.is_some_and(|ids| !ids.is_empty()) {
+            return DataRetrieverTask.run(context).await;
+        }
+
+        info!("Expanding query into {} variants", NUM_QUERY_VARIANTS);
+        let variants = generate_query_variants(&self.query, NUM_QUERY_VARIANTS)
+            .await
+            .map_err(|e| GraphError::TaskExecutionFailed(e.to_string()))?;
+        info!("Query variants: {:?}", variants);
+
+        context
+            .set(context_vars::QUERY_VARIANTS, variants.clone())
+            .await;
+
+        let config = Config::from_env();
+        let engines = build_engines(&config);
+        if engines.is_empty() {
+            return Err(GraphError::TaskExecutionFailed(
+                "No search engines configured or available".to_string(),
+            ));
+        }
+
+        let mut per_query_results: Vec<Vec<OrganicResult>> = Vec::with_capacity(variants.len());
+        let mut engine_errors: Vec<EngineErrorInfo> = Vec::new();
+
+        let outcomes = futures::future::join_all(
+            variants
+                .iter()
+                .map(|variant| aggregate_search(&engines, variant)),
+        )
+        .await;
+
+        for (variant, outcome) in variants.iter().zip(outcomes) {
+            match outcome {
+                Ok((results, mut errors)) => {
+                    per_query_results.push(results);
+                    engine_errors.append(&mut errors);
+                }
+                Err(e) => warn!("Retrieval failed for query variant '{}': {}", variant, e),
+            }
+        }
+
+        if !engine_errors.is_empty() {
+            warn!(
+                "{} search engine(s) failed across variants: {:?}",
+                engine_errors.len(),
+                engine_errors
+            );
+        }
+        context
+            .set(context_vars::ENGINE_ERRORS, engine_errors)
+            .await;
+
+        let fused = reciprocal_rank_fusion(&per_query_results, RRF_K);
+        info!(
+            "Fused {} per-variant result lists into {} ranked documents",
+            per_query_results.len(),
+            fused.len()
+        );
+
+        let top_results: Vec<OrganicResult> =
+            fused.into_iter().take(TOP_M_FUSED_RESULTS).collect();
+
+        let search_results = scrape_and_filter(&top_results)
+            .await
+            .map_err(|e| GraphError::TaskExecutionFailed(format!("Failed to scrape: {}", e)))?;
+
+        context
+            .set(context_vars::SEARCH_RESULTS, search_results)
+            .await;
+
+        Ok(())
+    }
+}
+
+const MULTI_QUERY_PROMPT: &str = r#"
+You are a search assistant, helping users find relevant documents with a web search engine.
+Given a user query, produce several diverse reformulations of it: vary the phrasing and the
+keyword sets so that each variant is likely to surface a different slice of relevant documents.
+Output exactly one reformulation per line, no numbering, no commas or other punctuation, no other text.
+"#;
+
+/// Ask the LLM for up to `k` diverse reformulations of `query`, one per line.
+/// Falls back to just the original query if the LLM returns no non-blank
+/// lines at all (e.g. on an empty response); a shorter list is used as is.
+async fn generate_query_variants(query: &str, k: usize) -> anyhow::Result<Vec<String>> {
+    let agent = get_llm_agent(MULTI_QUERY_PROMPT)?;
+    let q = format!("\nUser query:\n{}\n\nNumber of reformulations: {}", query, k);
+    let response = agent.prompt(q).await?;
+
+    let mut variants: Vec<String> = response
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .take(k)
+        .collect();
+
+    if variants.is_empty() {
+        variants.push(query.to_string());
+    }
+
+    Ok(variants)
+}
+
+/// Normalize a URL for fusion/dedup purposes: lowercase, strip a trailing
+/// slash and any fragment. Mirrors `search_engine::normalize_link`.
+fn normalize_link(link: &str) -> String {
+    let without_fragment = link.split('#').next().unwrap_or(link);
+    without_fragment.trim_end_matches('/').to_lowercase()
+}
+
+/// Fuse multiple per-query ranked result lists with Reciprocal Rank Fusion:
+/// `score(doc) = sum over queries of 1 / (k + rank)`, where `rank` is the
+/// document's 1-based position within that query's own result list.
+/// Documents are deduplicated by normalized URL (keeping the first-seen
+/// copy's metadata) and returned sorted by descending fused score.
+fn reciprocal_rank_fusion(per_query_results: &[Vec<OrganicResult>], k: f64) -> Vec<OrganicResult> {
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    let mut documents: HashMap<String, OrganicResult> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+
+    for results in per_query_results {
+        for (index, result) in results.iter().enumerate() {
+            let rank = index + 1;
+            let key = normalize_link(&result.link);
+            *scores.entry(key.clone()).or_insert(0.0) += 1.0 / (k + rank as f64);
+
+            documents
+                .entry(key.clone())
+                .and_modify(|existing| {
+                    for engine in &result.engines {
+                        if !existing.engines.contains(engine) {
+                            existing.engines.push(engine.clone());
+                        }
+                    }
+                })
+                .or_insert_with(|| {
+                    order.push(key.clone());
+                    result.clone()
+                });
+        }
+    }
+
+    let mut scored: Vec<(f64, OrganicResult)> = order
+        .into_iter()
+        .filter_map(|key| {
+            let doc = documents.remove(&key)?;
+            let score = scores.remove(&key).unwrap_or(0.0);
+            Some((score, doc))
+        })
+        .collect();
+
+    scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Re-number `position` to the fused rank, so downstream consumers of
+    // `OrganicResult` see a single consistent ranking regardless of which
+    // query variant originally surfaced each document.
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, mut doc))| {
+            doc.position = index + 1;
+            doc
+        })
+        .collect()
+}
@@ -0,0 +1,578 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+
+use crate::agent_workflow::OrganicResult;
+use crate::config::Config;
+use crate::user_agent::random_user_agent;
+
+/// Bound on how many engine queries run concurrently, so a large engine list
+/// can't exhaust outbound sockets.
+const MAX_CONCURRENT_ENGINES: usize = 8;
+
+/// Coarse classification of why an engine's query failed, so callers (and
+/// eventually the generated answer) can explain a partial result set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EngineErrorKind {
+    RequestTimeout,
+    QuotaExceeded,
+    UnexpectedError,
+}
+
+/// One engine's failure, surfaced alongside whatever other engines did succeed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineErrorInfo {
+    pub engine: String,
+    pub error: EngineErrorKind,
+    pub message: String,
+}
+
+fn classify_error(engine: &str, err: &anyhow::Error) -> EngineErrorInfo {
+    let message = err.to_string();
+    let kind = if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+        if reqwest_err.is_timeout() {
+            EngineErrorKind::RequestTimeout
+        } else if reqwest_err
+            .status()
+            .map(|s| s.as_u16() == 429)
+            .unwrap_or(false)
+        {
+            EngineErrorKind::QuotaExceeded
+        } else {
+            EngineErrorKind::UnexpectedError
+        }
+    } else if message.to_lowercase().contains("quota") {
+        EngineErrorKind::QuotaExceeded
+    } else {
+        EngineErrorKind::UnexpectedError
+    };
+
+    EngineErrorInfo {
+        engine: engine.to_string(),
+        error: kind,
+        message,
+    }
+}
+
+/// A pluggable web-search backend. Each implementor wraps a single provider
+/// (serper.dev, Google CSE, a Searx instance, ...) and returns results in the
+/// crate-wide [`OrganicResult`] shape so the retriever and aggregator don't
+/// need to know which provider answered.
+#[async_trait]
+pub trait SearchEngine: Send + Sync {
+    /// Short, stable identifier used for config selection and provenance (e.g. "serper").
+    fn name(&self) -> &'static str;
+
+    /// Run `query` against this engine and return its organic results, best match first.
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>>;
+}
+
+// -- serper.dev ---------------------------------------------------------
+
+/// `SearchEngine` backed by the serper.dev Google-search proxy, the default
+/// (and, until now, only) engine this service talked to.
+pub struct SerperEngine {
+    api_key: String,
+    site_filter: String,
+    num_results: u32,
+    recency_window: String,
+}
+
+impl SerperEngine {
+    const BASE_URL: &'static str = "https://google.serper.dev/search";
+
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let api_key = std::env::var("SERPER_API_KEY")
+            .map_err(|_| anyhow::anyhow!("SERPER_API_KEY not set"))?;
+        Ok(Self {
+            api_key,
+            site_filter: config.search_site_filter.clone(),
+            num_results: config.search_num_results,
+            recency_window: config.search_recency_window.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SerperSearchResponse {
+    #[serde(default)]
+    organic: Vec<SerperOrganicResult>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SerperOrganicResult {
+    title: String,
+    link: String,
+    #[serde(default)]
+    snippet: String,
+    #[serde(default)]
+    position: usize,
+}
+
+#[async_trait]
+impl SearchEngine for SerperEngine {
+    fn name(&self) -> &'static str {
+        "serper"
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>> {
+        let client = Config::from_env().http_client()?;
+        let query_encoded = query.split_whitespace().collect::<Vec<_>>().join("+");
+        let url = format!(
+            "{}?q={}+{}&apiKey={}&num={}&tbs={}",
+            Self::BASE_URL,
+            query_encoded,
+            self.site_filter,
+            self.api_key,
+            self.num_results,
+            self.recency_window
+        );
+        info!("[serper] Executing search with URL: {}", url);
+
+        let response = client.get(&url).send().await?;
+        info!("[serper] Received response status: {}", response.status());
+        let body = response.text().await?;
+        debug!("[serper] Response body: {}", body);
+
+        let parsed: SerperSearchResponse = serde_json::from_str(&body)?;
+        Ok(parsed
+            .organic
+            .into_iter()
+            .map(|r| OrganicResult {
+                title: r.title,
+                link: r.link,
+                snippet: r.snippet,
+                display_link: None,
+                position: r.position,
+                engines: vec!["serper".to_string()],
+            })
+            .collect())
+    }
+}
+
+// -- Google Custom Search -------------------------------------------------
+
+/// `SearchEngine` backed by the Google Custom Search JSON API.
+pub struct GoogleCseEngine {
+    api_key: String,
+    cx: String,
+    site_filter: String,
+    num_results: u32,
+}
+
+impl GoogleCseEngine {
+    const BASE_URL: &'static str = "https://www.googleapis.com/customsearch/v1";
+
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let api_key = std::env::var("GOOGLE_CSE_API_KEY")
+            .map_err(|_| anyhow::anyhow!("GOOGLE_CSE_API_KEY not set"))?;
+        let cx = std::env::var("GOOGLE_CSE_CX")
+            .map_err(|_| anyhow::anyhow!("GOOGLE_CSE_CX not set"))?;
+        Ok(Self {
+            api_key,
+            cx,
+            site_filter: config.search_site_filter.clone(),
+            num_results: config.search_num_results,
+        })
+    }
+}
+
+#[async_trait]
+impl SearchEngine for GoogleCseEngine {
+    fn name(&self) -> &'static str {
+        "google"
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>> {
+        let client = Config::from_env().http_client()?;
+        let full_query = format!("{} {}", query, self.site_filter);
+        let url = format!(
+            "{}?key={}&cx={}&q={}&num={}",
+            Self::BASE_URL,
+            self.api_key,
+            self.cx,
+            urlencoding::encode(&full_query),
+            self.num_results.min(10)
+        );
+        info!("[google] Executing search with URL: {}", url);
+
+        let response = client.get(&url).send().await?;
+        let body = response.text().await?;
+        debug!("[google] Response body: {}", body);
+
+        let parsed: crate::agent_workflow::SearchResponse = serde_json::from_str(&body)?;
+        Ok(parsed
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut r)| {
+                r.position = i + 1;
+                r.engines = vec!["google".to_string()];
+                r
+            })
+            .collect())
+    }
+}
+
+// -- DuckDuckGo -------------------------------------------------------------
+
+/// `SearchEngine` backed by DuckDuckGo's lightweight HTML results page
+/// (no API key required).
+pub struct DuckDuckGoEngine {
+    site_filter: String,
+}
+
+impl DuckDuckGoEngine {
+    const BASE_URL: &'static str = "https://html.duckduckgo.com/html/";
+
+    pub fn new(config: &Config) -> Self {
+        Self {
+            site_filter: config.search_site_filter.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl SearchEngine for DuckDuckGoEngine {
+    fn name(&self) -> &'static str {
+        "duckduckgo"
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>> {
+        let config = Config::from_env();
+        let client = config.http_client()?;
+        let full_query = format!("{} {}", query, self.site_filter);
+        let response = client
+            .get(Self::BASE_URL)
+            .query(&[("q", full_query.as_str())])
+            .header("User-Agent", random_user_agent(&config))
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .send()
+            .await?;
+        let body = response.text().await?;
+        debug!("[duckduckgo] Response body length: {}", body.len());
+
+        Ok(parse_duckduckgo_html(&body))
+    }
+}
+
+/// Parse a DuckDuckGo HTML results page into [`OrganicResult`]s, decoding the
+/// real redirect target from the `uddg=` query parameter of each result link.
+fn parse_duckduckgo_html(html: &str) -> Vec<OrganicResult> {
+    use scraper::{Html, Selector};
+
+    let document = Html::parse_document(html);
+    let result_selector = Selector::parse(".result").unwrap();
+    let title_selector = Selector::parse(".result__a").unwrap();
+    let snippet_selector = Selector::parse(".result__snippet").unwrap();
+
+    let mut results = Vec::new();
+    for (i, result) in document.select(&result_selector).enumerate() {
+        let Some(title_el) = result.select(&title_selector).next() else {
+            continue;
+        };
+        let title = title_el.text().collect::<String>();
+        let Some(href) = title_el.value().attr("href") else {
+            continue;
+        };
+        let link = decode_uddg_redirect(href);
+        let snippet = result
+            .select(&snippet_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        results.push(OrganicResult {
+            title,
+            link,
+            snippet,
+            display_link: None,
+            position: i + 1,
+            engines: vec!["duckduckgo".to_string()],
+        });
+    }
+    results
+}
+
+/// Recover the canonical URL from a DuckDuckGo redirect href by
+/// percent-decoding its `uddg` query parameter; falls back to the raw href.
+fn decode_uddg_redirect(href: &str) -> String {
+    let query = href.split('?').nth(1).unwrap_or("");
+    for pair in query.split('&') {
+        if let Some(value) = pair.strip_prefix("uddg=") {
+            if let Ok(decoded) = urlencoding::decode(value) {
+                return decoded.into_owned();
+            }
+        }
+    }
+    href.to_string()
+}
+
+// -- Google HTML scraping ----------------------------------------------------
+
+/// `SearchEngine` that scrapes Google's ordinary web results page instead of
+/// calling the billed Custom Search JSON API, so self-hosters get a
+/// zero-cost Google-backed engine (and a fallback when
+/// `GOOGLE_CSE_API_KEY` is unset).
+pub struct GoogleHtmlEngine {
+    site_filter: String,
+    num_results: u32,
+}
+
+impl GoogleHtmlEngine {
+    const BASE_URL: &'static str = "https://www.google.com/search";
+
+    pub fn new(config: &Config) -> Self {
+        Self {
+            site_filter: config.search_site_filter.clone(),
+            num_results: config.search_num_results,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchEngine for GoogleHtmlEngine {
+    fn name(&self) -> &'static str {
+        "google_html"
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>> {
+        let config = Config::from_env();
+        let client = config.http_client()?;
+        let full_query = format!("{} {}", query, self.site_filter);
+        let response = client
+            .get(Self::BASE_URL)
+            .query(&[
+                ("q", full_query.as_str()),
+                ("num", self.num_results.to_string().as_str()),
+            ])
+            .header("User-Agent", random_user_agent(&config))
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .send()
+            .await?;
+        let body = response.text().await?;
+        debug!("[google_html] Response body length: {}", body.len());
+
+        Ok(parse_google_html(&body))
+    }
+}
+
+/// Parse a Google web-results page into [`OrganicResult`]s using the `.g`/
+/// `.tF2Cxc` result containers Google's markup has used historically;
+/// malformed or missing fields simply drop that result rather than failing
+/// the whole parse.
+fn parse_google_html(html: &str) -> Vec<OrganicResult> {
+    use scraper::{Html, Selector};
+
+    let document = Html::parse_document(html);
+    let result_selector = Selector::parse("div.g, div.tF2Cxc").unwrap();
+    let title_selector = Selector::parse("h3").unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+    let snippet_selector = Selector::parse(".VwiC3b, .IsZvec").unwrap();
+
+    let mut results = Vec::new();
+    for (i, result) in document.select(&result_selector).enumerate() {
+        let Some(title_el) = result.select(&title_selector).next() else {
+            continue;
+        };
+        let title = title_el.text().collect::<String>();
+        let Some(link) = result
+            .select(&link_selector)
+            .find_map(|a| a.value().attr("href"))
+        else {
+            continue;
+        };
+        let snippet = result
+            .select(&snippet_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        results.push(OrganicResult {
+            title,
+            link: link.to_string(),
+            snippet,
+            display_link: None,
+            position: i + 1,
+            engines: vec!["google_html".to_string()],
+        });
+    }
+    results
+}
+
+// -- Searx ------------------------------------------------------------------
+
+/// `SearchEngine` backed by a self-hosted Searx/SearXNG instance's JSON API.
+pub struct SearxEngine {
+    instance_url: String,
+    site_filter: String,
+}
+
+impl SearxEngine {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let instance_url = std::env::var("SEARX_INSTANCE_URL")
+            .map_err(|_| anyhow::anyhow!("SEARX_INSTANCE_URL not set"))?;
+        Ok(Self {
+            instance_url,
+            site_filter: config.search_site_filter.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SearxResponse {
+    #[serde(default)]
+    results: Vec<SearxResult>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SearxResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl SearchEngine for SearxEngine {
+    fn name(&self) -> &'static str {
+        "searx"
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<OrganicResult>> {
+        let client = Config::from_env().http_client()?;
+        let full_query = format!("{} {}", query, self.site_filter);
+        let url = format!("{}/search", self.instance_url.trim_end_matches('/'));
+        let response = client
+            .get(&url)
+            .query(&[("q", full_query.as_str()), ("format", "json")])
+            .send()
+            .await?;
+        let body = response.text().await?;
+        debug!("[searx] Response body: {}", body);
+
+        let parsed: SearxResponse = serde_json::from_str(&body)?;
+        Ok(parsed
+            .results
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| OrganicResult {
+                title: r.title,
+                link: r.url,
+                snippet: r.content,
+                display_link: None,
+                position: i + 1,
+                engines: vec!["searx".to_string()],
+            })
+            .collect())
+    }
+}
+
+// -- aggregation ----------------------------------------------------------
+
+/// Maps a configured engine name (e.g. `"duckduckgo"`, `"google"`, `"serper"`,
+/// `"searx"`) to the `SearchEngine` that implements it.
+pub struct EngineHandler;
+
+impl EngineHandler {
+    pub fn resolve(name: &str, config: &Config) -> anyhow::Result<Box<dyn SearchEngine>> {
+        match name {
+            "serper" => Ok(Box::new(SerperEngine::new(config)?)),
+            "google" => Ok(Box::new(GoogleCseEngine::new(config)?)),
+            "duckduckgo" => Ok(Box::new(DuckDuckGoEngine::new(config))),
+            "google_html" => Ok(Box::new(GoogleHtmlEngine::new(config))),
+            "searx" => Ok(Box::new(SearxEngine::new(config)?)),
+            other => Err(anyhow::anyhow!("Unknown search engine '{}'", other)),
+        }
+    }
+}
+
+/// Build the list of enabled engines from `Config::search_engines`.
+pub fn build_engines(config: &Config) -> Vec<Box<dyn SearchEngine>> {
+    let mut engines: Vec<Box<dyn SearchEngine>> = Vec::new();
+    for name in &config.search_engines {
+        match EngineHandler::resolve(name, config) {
+            Ok(engine) => engines.push(engine),
+            Err(e) => tracing::warn!("Skipping search engine '{}': {}", name, e),
+        }
+    }
+    engines
+}
+
+/// Normalize a URL for deduplication purposes: lowercase, strip a trailing
+/// slash and any fragment.
+fn normalize_link(link: &str) -> String {
+    let without_fragment = link.split('#').next().unwrap_or(link);
+    without_fragment.trim_end_matches('/').to_lowercase()
+}
+
+/// Query every engine concurrently (bounded by [`MAX_CONCURRENT_ENGINES`]),
+/// deduplicate results by normalized link (keeping first-seen ordering,
+/// merging provenance), and re-rank the merged list by ascending position.
+///
+/// Tolerates partial failures: a per-engine error is collected rather than
+/// aborting the whole call, and only returned as `Err` when *every* engine fails.
+pub async fn aggregate_search(
+    engines: &[Box<dyn SearchEngine>],
+    query: &str,
+) -> anyhow::Result<(Vec<OrganicResult>, Vec<EngineErrorInfo>)> {
+    let outcomes: Vec<(String, anyhow::Result<Vec<OrganicResult>>)> =
+        futures::stream::iter(engines.iter())
+            .map(|engine| async move { (engine.name().to_string(), engine.search(query).await) })
+            .buffer_unordered(MAX_CONCURRENT_ENGINES)
+            .collect()
+            .await;
+
+    let mut merged: HashMap<String, OrganicResult> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut errors: Vec<EngineErrorInfo> = Vec::new();
+
+    for (engine_name, outcome) in outcomes {
+        let items = match outcome {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("Engine '{}' failed: {}", engine_name, e);
+                errors.push(classify_error(&engine_name, &e));
+                continue;
+            }
+        };
+
+        for item in items {
+            let key = normalize_link(&item.link);
+            if let Some(existing) = merged.get_mut(&key) {
+                existing.position = existing.position.min(item.position);
+                for engine in item.engines {
+                    if !existing.engines.contains(&engine) {
+                        existing.engines.push(engine);
+                    }
+                }
+            } else {
+                order.push(key.clone());
+                merged.insert(key, item);
+            }
+        }
+    }
+
+    if !errors.is_empty() && errors.len() == engines.len() {
+        return Err(anyhow::anyhow!(
+            "All {} search engine(s) failed: {}",
+            errors.len(),
+            errors
+                .iter()
+                .map(|e| format!("{}: {}", e.engine, e.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ));
+    }
+
+    let mut organic: Vec<OrganicResult> = order
+        .into_iter()
+        .filter_map(|key| merged.remove(&key))
+        .collect();
+    organic.sort_by_key(|r| r.position);
+
+    Ok((organic, errors))
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+/// Drops scraped pages that are near-duplicates of ones already kept, so
+/// boilerplate-heavy or paginated near-identical pages don't waste the LLM
+/// context window in `GenerateAnswerTask`.
+///
+/// Each page is tokenized into lowercased word terms and scored as a
+/// TF-IDF vector against a document-frequency table built incrementally
+/// from every page seen so far (kept or not). A page is dropped when its
+/// cosine similarity to any already-kept page meets `threshold`.
+pub struct SimilarityFilter {
+    threshold: f64,
+    document_count: usize,
+    document_frequency: HashMap<String, usize>,
+    kept_term_frequencies: Vec<HashMap<String, f64>>,
+}
+
+impl SimilarityFilter {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            document_count: 0,
+            document_frequency: HashMap::new(),
+            kept_term_frequencies: Vec::new(),
+        }
+    }
+
+    /// Score `text` against every page kept so far, update the
+    /// document-frequency table, and return whether it should be kept
+    /// (`false` means it's a near-duplicate of an already-kept page).
+    pub fn should_keep(&mut self, text: &str) -> bool {
+        let term_frequency = term_frequencies(text);
+        if term_frequency.is_empty() {
+            return true;
+        }
+
+        self.document_count += 1;
+        for term in term_frequency.keys() {
+            *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+
+        let candidate_vector = self.tfidf_vector(&term_frequency);
+
+        let is_near_duplicate = self.kept_term_frequencies.iter().any(|kept_tf| {
+            cosine_similarity(&candidate_vector, &self.tfidf_vector(kept_tf)) >= self.threshold
+        });
+
+        if is_near_duplicate {
+            return false;
+        }
+
+        self.kept_term_frequencies.push(term_frequency);
+        true
+    }
+
+    /// Weight each term's raw frequency by a smoothed `ln((N+1)/(df+1)) + 1`,
+    /// using the document-frequency table as it stands right now. The
+    /// smoothing keeps the weight positive even when a term's `df` equals
+    /// `N` (e.g. two near-identical pages sharing every term), which a bare
+    /// `ln(N/df)` would zero out and which would in turn zero every
+    /// cosine-similarity score between them.
+    fn tfidf_vector(&self, term_frequency: &HashMap<String, f64>) -> HashMap<String, f64> {
+        term_frequency
+            .iter()
+            .map(|(term, tf)| {
+                let df = self.document_frequency.get(term).copied().unwrap_or(0);
+                let idf = ((self.document_count + 1) as f64 / (df + 1) as f64).ln() + 1.0;
+                (term.clone(), tf * idf)
+            })
+            .collect()
+    }
+}
+
+impl Default for SimilarityFilter {
+    fn default() -> Self {
+        Self::new(0.95)
+    }
+}
+
+fn term_frequencies(text: &str) -> HashMap<String, f64> {
+    let mut term_frequency = HashMap::new();
+    for token in text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.len() > 2)
+    {
+        *term_frequency.entry(token.to_string()).or_insert(0.0) += 1.0;
+    }
+    term_frequency
+}
+
+fn cosine_similarity(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let dot: f64 = a
+        .iter()
+        .map(|(term, weight)| weight * b.get(term).copied().unwrap_or(0.0))
+        .sum();
+    let norm_a = a.values().map(|w| w * w).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|w| w * w).sum::<f64>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_first_page_unconditionally() {
+        let mut filter = SimilarityFilter::new(0.95);
+        assert!(filter.should_keep("Gaza Strip humanitarian crisis report details"));
+    }
+
+    #[test]
+    fn test_drops_near_identical_pages() {
+        let mut filter = SimilarityFilter::new(0.9);
+        let page = "Gaza Strip humanitarian crisis report details aid access checkpoints";
+
+        assert!(filter.should_keep(page));
+        assert!(!filter.should_keep(page));
+    }
+
+    #[test]
+    fn test_keeps_distinct_pages() {
+        let mut filter = SimilarityFilter::new(0.95);
+
+        assert!(filter.should_keep(
+            "Gaza Strip humanitarian crisis report details aid access checkpoints"
+        ));
+        assert!(filter.should_keep(
+            "West Bank settlement expansion land confiscation permits demolition orders"
+        ));
+    }
+}
@@ -5,8 +5,11 @@ use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::agent_workflow::ScraperSingleton;
+use crate::config::Config;
+use crate::graphql::{build_schema, graphiql, graphql_handler};
+use crate::jobs::JobQueue;
+use crate::metrics::{install_recorder, metrics_handler};
 use crate::routes::create_routes;
-use crate::scraper::WebScraper;
 
 /// Initialize tracing and logging for the application
 pub fn init_tracing() {
@@ -26,12 +29,44 @@ pub async fn create_app() -> Result<Router, anyhow::Error> {
     // Initialize shared scraper instance×–
     info!("Initializing web scraper...");
     ScraperSingleton::init().await?;
-    let scraper = WebScraper::new().await?;
+    let scraper = (*ScraperSingleton::get()?).clone();
     info!("Web scraper initialized successfully");
 
+    // Spawn the agent job worker pool
+    let config = Config::from_env();
+    info!(
+        "Starting agent job queue with {} workers",
+        config.worker_concurrency
+    );
+    let job_queue = JobQueue::spawn(config.worker_concurrency);
+
+    // Install the Prometheus recorder and mount /metrics on its own router so
+    // it isn't wrapped by the permissive CORS layer used for the agent API.
+    let prometheus_handle = install_recorder();
+    let metrics_router = Router::new()
+        .route("/metrics", axum::routing::get(metrics_handler))
+        .layer(Extension(prometheus_handle));
+
+    // GraphQL mirrors the REST operations and adds a file-upload mutation
+    // for injecting ad-hoc documents into the workflow context.
+    let graphql_schema = build_schema(scraper.clone());
+    let graphql_router = Router::new()
+        .route(
+            "/graphql",
+            axum::routing::get(graphiql).post(graphql_handler),
+        )
+        .layer(axum::extract::DefaultBodyLimit::disable())
+        .layer(Extension(graphql_schema));
+
     Ok(Router::new()
         .merge(create_routes())
         .layer(Extension(scraper)) // Add scraper as shared state
+        .layer(Extension(job_queue)) // Add agent job queue as shared state
         //.layer(TraceLayer::new_for_http())
-        .layer(CorsLayer::permissive()))
+        .layer(CorsLayer::permissive())
+        // Negotiated response compression for the large aggregated answers
+        // and source lists `/api/agent1` returns.
+        .layer(config.compression_layer())
+        .merge(metrics_router)
+        .merge(graphql_router))
 }
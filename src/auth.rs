@@ -0,0 +1,84 @@
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+#[derive(Debug, Serialize)]
+struct AuthErrorResponse {
+    error: &'static str,
+    message: &'static str,
+}
+
+fn auth_error(status: StatusCode, error: &'static str, message: &'static str) -> Response {
+    (status, Json(AuthErrorResponse { error, message })).into_response()
+}
+
+fn hash_key(key: &str) -> String {
+    let digest = Sha256::digest(key.as_bytes());
+    format!("{:x}", digest)
+}
+
+/// Tower/axum middleware validating an `Authorization: Bearer <key>` or
+/// `X-API-Key` header against `Config::api_key`. No-ops (passes the request
+/// through) when `Config::auth_enabled` is false, so deployments can opt in.
+pub async fn require_api_key(request: Request, next: Next) -> Response {
+    let config = Config::from_env();
+
+    if !config.auth_enabled {
+        return next.run(request).await;
+    }
+
+    let Some(expected_key) = config.api_key.as_deref() else {
+        return auth_error(
+            StatusCode::FORBIDDEN,
+            "UNKNOWN_API_KEY",
+            "Unknown API key",
+        );
+    };
+
+    let provided_key = request
+        .headers()
+        .get("X-API-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| {
+            request
+                .headers()
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::to_string)
+        });
+
+    let Some(provided_key) = provided_key else {
+        return auth_error(
+            StatusCode::FORBIDDEN,
+            "MISSING_API_KEY",
+            "Can't auth without API key",
+        );
+    };
+
+    if hash_key(&provided_key) != hash_key(expected_key) {
+        return auth_error(StatusCode::FORBIDDEN, "UNKNOWN_API_KEY", "Unknown API key");
+    }
+
+    next.run(request).await
+}
+
+/// Fallback handler for any `/{version}/...` path that doesn't match a
+/// mounted version router (e.g. `/v2/scrape`), so the scraper surface can
+/// evolve without silently 404ing on clients pinned to an old/unsupported
+/// version.
+pub async fn unknown_api_version() -> Response {
+    auth_error(
+        StatusCode::NOT_FOUND,
+        "UNKNOWN_API_VERSION",
+        "Unknown API version",
+    )
+}
@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Generic cache abstraction for memoizing expensive network/LLM calls.
+///
+/// Implementors store a JSON-serialized value under a string key with a TTL.
+/// Callers are expected to derive the key themselves (e.g. by hashing a
+/// normalized query or URL) so the trait stays storage-agnostic.
+#[async_trait]
+pub trait Cache: Send + Sync {
+    /// Fetch the raw JSON string stored under `key`, if present and not expired.
+    async fn get_raw(&self, key: &str) -> anyhow::Result<Option<String>>;
+
+    /// Store `value` (already serialized to JSON) under `key` for `ttl`.
+    async fn set_raw(&self, key: &str, value: String, ttl: Duration) -> anyhow::Result<()>;
+}
+
+/// Convenience helpers built on top of [`Cache::get_raw`]/[`Cache::set_raw`]
+/// that handle (de)serialization for callers.
+#[async_trait]
+pub trait CacheExt: Cache {
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> anyhow::Result<Option<T>> {
+        match self.get_raw(key).await? {
+            Some(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn set<T: Serialize + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        let raw = serde_json::to_string(value)?;
+        self.set_raw(key, raw, ttl).await
+    }
+}
+
+impl<C: Cache + ?Sized> CacheExt for C {}
+
+/// Derive a stable cache key from an arbitrary piece of text (a query or a
+/// URL), normalized by lowercasing and trimming so equivalent inputs share
+/// an entry.
+pub fn cache_key(prefix: &str, input: &str) -> String {
+    let normalized = input.trim().to_lowercase();
+    let digest = md5::compute(normalized.as_bytes());
+    format!("{}:{:x}", prefix, digest)
+}
+
+/// Tracks cumulative cache hits/misses per cache "kind" (e.g. "search",
+/// "answer") and logs a running total on every access via `tracing`.
+pub fn record_cache_access(kind: &str, hit: bool) {
+    use std::sync::{Mutex, OnceLock};
+
+    static COUNTERS: OnceLock<Mutex<HashMap<String, (u64, u64)>>> = OnceLock::new();
+    let counters = COUNTERS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut counters = counters.lock().unwrap();
+    let entry = counters.entry(kind.to_string()).or_insert((0, 0));
+    if hit {
+        entry.0 += 1;
+    } else {
+        entry.1 += 1;
+    }
+    tracing::info!(
+        cache = kind,
+        hits = entry.0,
+        misses = entry.1,
+        "cache access"
+    );
+}
+
+struct InMemoryEntry {
+    value: String,
+    expires_at: Instant,
+}
+
+/// In-process cache backed by a `HashMap`, used when no `REDIS_URL` is configured.
+#[derive(Clone, Default)]
+pub struct InMemoryCache {
+    entries: Arc<Mutex<HashMap<String, InMemoryEntry>>>,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Cache for InMemoryCache {
+    async fn get_raw(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let mut entries = self.entries.lock().await;
+        if let Some(entry) = entries.get(key) {
+            if entry.expires_at > Instant::now() {
+                return Ok(Some(entry.value.clone()));
+            }
+            entries.remove(key);
+        }
+        Ok(None)
+    }
+
+    async fn set_raw(&self, key: &str, value: String, ttl: Duration) -> anyhow::Result<()> {
+        let mut entries = self.entries.lock().await;
+        entries.insert(
+            key.to_string(),
+            InMemoryEntry {
+                value,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct DiskEntry {
+    value: String,
+    expires_at_unix_ms: u128,
+}
+
+/// JSON-on-disk [`Cache`] implementation: one file per key under a
+/// directory, storing the serialized value alongside its expiry so entries
+/// survive process restarts. Intended for opt-in, longer-lived caching
+/// (e.g. scraped page text) where an in-memory or Redis cache would be the
+/// wrong tradeoff.
+#[derive(Clone)]
+pub struct DiskCache {
+    dir: std::path::PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path_for(&self, key: &str) -> std::path::PathBuf {
+        self.dir.join(format!("{}.json", key.replace([':', '/'], "_")))
+    }
+
+    /// Remove a cached entry, if any, regardless of whether it has expired.
+    pub async fn invalidate(&self, key: &str) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(self.path_for(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Cache for DiskCache {
+    async fn get_raw(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let path = self.path_for(key);
+        let raw = match tokio::fs::read_to_string(&path).await {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let entry: DiskEntry = serde_json::from_str(&raw)?;
+        let now_unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis();
+
+        if now_unix_ms >= entry.expires_at_unix_ms {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Ok(None);
+        }
+
+        Ok(Some(entry.value))
+    }
+
+    async fn set_raw(&self, key: &str, value: String, ttl: Duration) -> anyhow::Result<()> {
+        let expires_at_unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis()
+            + ttl.as_millis();
+
+        let raw = serde_json::to_string(&DiskEntry {
+            value,
+            expires_at_unix_ms,
+        })?;
+
+        tokio::fs::write(self.path_for(key), raw).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "redis-cache")]
+pub mod redis_cache {
+    use super::Cache;
+    use async_trait::async_trait;
+    use redis::AsyncCommands;
+    use std::time::Duration;
+
+    /// Redis-backed [`Cache`] implementation, enabled via the `redis-cache` feature.
+    #[derive(Clone)]
+    pub struct RedisCache {
+        client: redis::Client,
+    }
+
+    impl RedisCache {
+        pub fn new(redis_url: &str) -> anyhow::Result<Self> {
+            let client = redis::Client::open(redis_url)?;
+            Ok(Self { client })
+        }
+    }
+
+    #[async_trait]
+    impl Cache for RedisCache {
+        async fn get_raw(&self, key: &str) -> anyhow::Result<Option<String>> {
+            let mut conn = self.client.get_multiplexed_async_connection().await?;
+            let value: Option<String> = conn.get(key).await?;
+            Ok(value)
+        }
+
+        async fn set_raw(&self, key: &str, value: String, ttl: Duration) -> anyhow::Result<()> {
+            let mut conn = self.client.get_multiplexed_async_connection().await?;
+            let _: () = conn.set_ex(key, value, ttl.as_secs()).await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "redis-cache")]
+pub use redis_cache::RedisCache;
+
+/// Build the cache backend configured by [`crate::config::Config`]: a
+/// [`RedisCache`] when `redis_url` is set and the `redis-cache` feature is
+/// enabled, otherwise the in-memory default.
+pub fn build_cache(_redis_url: Option<&str>) -> Arc<dyn Cache> {
+    #[cfg(feature = "redis-cache")]
+    if let Some(url) = _redis_url {
+        match RedisCache::new(url) {
+            Ok(cache) => return Arc::new(cache),
+            Err(e) => {
+                tracing::warn!("Failed to connect to Redis at {}, falling back to in-memory cache: {}", url, e);
+            }
+        }
+    }
+
+    Arc::new(InMemoryCache::new())
+}
+
+static SHARED_CACHE: once_cell::sync::OnceCell<Arc<dyn Cache>> = once_cell::sync::OnceCell::new();
+
+/// Process-wide [`Cache`] instance, built once from `redis_url` on first use
+/// and reused for every later call. Callers like `rate_limit_middleware` and
+/// `retrieve_data` used to call [`build_cache`] directly on every request,
+/// which meant a fresh, empty `InMemoryCache` per call whenever `REDIS_URL`
+/// wasn't set — no state (rate-limit timestamps, cached answers) ever
+/// survived past the request that created it. This memoizes that factory
+/// the same way [`crate::agent_workflow::ScraperSingleton`] memoizes the
+/// `WebScraper`.
+pub fn shared_cache(redis_url: Option<&str>) -> Arc<dyn Cache> {
+    SHARED_CACHE.get_or_init(|| build_cache(redis_url)).clone()
+}
@@ -6,6 +6,62 @@ pub struct Config {
     pub host: String,
     pub port: u16,
     pub log_level: String,
+    /// Optional Redis connection URL used by the caching subsystem; when unset
+    /// the in-memory cache backend is used instead.
+    pub redis_url: Option<String>,
+    /// Default TTL, in seconds, for cached search results and scraped pages.
+    pub cache_ttl_seconds: u64,
+    /// Number of background workers draining the agent job queue.
+    pub worker_concurrency: usize,
+    /// Whether the `/v1` API key middleware is enforced.
+    pub auth_enabled: bool,
+    /// The API key clients must present via `X-API-Key`/`Authorization: Bearer` when auth is enabled.
+    pub api_key: Option<String>,
+    /// Timeout for establishing outbound HTTP connections, in milliseconds.
+    pub http_connect_timeout_ms: u64,
+    /// Timeout for an entire outbound HTTP request, in milliseconds.
+    pub http_request_timeout_ms: u64,
+    /// Ordered list of enabled search engine names (e.g. `["serper"]`), matched by `search_engine::build_engines`.
+    pub search_engines: Vec<String>,
+    /// Site-restriction filter applied to search queries (e.g. `site:www.btselem.org`).
+    pub search_site_filter: String,
+    /// Number of results requested per engine per query.
+    pub search_num_results: u32,
+    /// Recency window applied to search queries, in the engine's own syntax (e.g. `qdr:3y`).
+    pub search_recency_window: String,
+    /// Sliding-window size, in seconds, over which `/api/agent1` requests are counted per client.
+    pub rate_limit_window_seconds: u64,
+    /// Maximum number of `/api/agent1` requests a single client may make within `rate_limit_window_seconds`.
+    pub rate_limit_max_requests: u32,
+    /// Override pool of User-Agent strings for outbound engine/scrape requests; falls back to the built-in defaults when empty.
+    pub user_agents: Vec<String>,
+    /// Response compression algorithms to negotiate via `Accept-Encoding` (any of `gzip`, `br`, `zstd`).
+    pub compression_algorithms: Vec<String>,
+    /// Responses smaller than this many bytes are sent uncompressed.
+    pub compression_min_size_bytes: u16,
+    /// Number of long-lived headless Chrome instances in the scraper's browser pool.
+    pub scraper_pool_size: usize,
+    /// Maximum number of scrape operations allowed to run concurrently across the pool.
+    pub scraper_max_concurrent: usize,
+    /// Whether `WebScraper` fetches and honors each host's `robots.txt`
+    /// before navigating, skipping disallowed paths and using any
+    /// `Crawl-delay` directive in place of the fixed politeness delay.
+    pub respect_robots_txt: bool,
+    /// Whether scraped pages too similar to one already kept are dropped
+    /// before `SEARCH_RESULTS` is populated, via `SimilarityFilter`.
+    pub dedupe_similar_pages: bool,
+    /// Cosine-similarity threshold (0.0-1.0) at or above which a scraped page
+    /// counts as a near-duplicate of an already-kept page.
+    pub near_duplicate_threshold: f64,
+    /// Whether `create_agent_workflow` uses `MultiQueryExpandTask` (multiple
+    /// query reformulations fused with Reciprocal Rank Fusion) in place of
+    /// the default `QueryEnhanceTask` + `DataRetrieverTask` pair.
+    pub use_multi_query_expansion: bool,
+    /// Overall deadline for a single agent workflow execution
+    /// (`graph.execute()`), in milliseconds. Bounds the whole pipeline -
+    /// including work like headless-Chrome scraping that isn't covered by
+    /// `http_request_timeout_ms` - so a hung step can't pin a worker forever.
+    pub workflow_timeout_ms: u64,
 }
 
 impl Config {
@@ -18,9 +74,110 @@ impl Config {
                 .expect("PORT must be a valid number"),
             log_level: env::var("RUST_LOG")
                 .unwrap_or_else(|_| "rs_rag_svc=info,tower_http=debug".to_string()),
+            redis_url: env::var("REDIS_URL").ok(),
+            cache_ttl_seconds: env::var("CACHE_TTL_SECONDS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .expect("CACHE_TTL_SECONDS must be a valid number"),
+            worker_concurrency: env::var("WORKER_CONCURRENCY")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .expect("WORKER_CONCURRENCY must be a valid number"),
+            auth_enabled: env::var("AUTH_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            api_key: env::var("API_KEY").ok(),
+            http_connect_timeout_ms: env::var("HTTP_CONNECT_TIMEOUT_MS")
+                .unwrap_or_else(|_| "5000".to_string())
+                .parse()
+                .expect("HTTP_CONNECT_TIMEOUT_MS must be a valid number"),
+            http_request_timeout_ms: env::var("HTTP_REQUEST_TIMEOUT_MS")
+                .unwrap_or_else(|_| "15000".to_string())
+                .parse()
+                .expect("HTTP_REQUEST_TIMEOUT_MS must be a valid number"),
+            search_engines: env::var("SEARCH_ENGINES")
+                .unwrap_or_else(|_| "serper".to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            search_site_filter: env::var("SEARCH_SITE_FILTER")
+                .unwrap_or_else(|_| "site:www.btselem.org".to_string()),
+            search_num_results: env::var("SEARCH_NUM_RESULTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("SEARCH_NUM_RESULTS must be a valid number"),
+            search_recency_window: env::var("SEARCH_RECENCY_WINDOW")
+                .unwrap_or_else(|_| "qdr:3y".to_string()),
+            rate_limit_window_seconds: env::var("RATE_LIMIT_WINDOW_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .expect("RATE_LIMIT_WINDOW_SECONDS must be a valid number"),
+            rate_limit_max_requests: env::var("RATE_LIMIT_MAX_REQUESTS")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .expect("RATE_LIMIT_MAX_REQUESTS must be a valid number"),
+            user_agents: env::var("USER_AGENTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            compression_algorithms: env::var("COMPRESSION_ALGORITHMS")
+                .unwrap_or_else(|_| "gzip,br,zstd".to_string())
+                .split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            compression_min_size_bytes: env::var("COMPRESSION_MIN_SIZE_BYTES")
+                .unwrap_or_else(|_| "256".to_string())
+                .parse()
+                .expect("COMPRESSION_MIN_SIZE_BYTES must be a valid number"),
+            scraper_pool_size: env::var("SCRAPER_POOL_SIZE")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .expect("SCRAPER_POOL_SIZE must be a valid number"),
+            scraper_max_concurrent: env::var("SCRAPER_MAX_CONCURRENT")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .expect("SCRAPER_MAX_CONCURRENT must be a valid number"),
+            respect_robots_txt: env::var("RESPECT_ROBOTS_TXT")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            dedupe_similar_pages: env::var("DEDUPE_SIMILAR_PAGES")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            near_duplicate_threshold: env::var("NEAR_DUPLICATE_THRESHOLD")
+                .unwrap_or_else(|_| "0.95".to_string())
+                .parse()
+                .expect("NEAR_DUPLICATE_THRESHOLD must be a valid number"),
+            use_multi_query_expansion: env::var("USE_MULTI_QUERY_EXPANSION")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            workflow_timeout_ms: env::var("WORKFLOW_TIMEOUT_MS")
+                .unwrap_or_else(|_| "60000".to_string())
+                .parse()
+                .expect("WORKFLOW_TIMEOUT_MS must be a valid number"),
         }
     }
 
+    /// Build a `reqwest::Client` configured with this instance's connect and
+    /// request timeouts, ready for outbound calls to search engines and
+    /// other third-party APIs. The TLS backend itself (`native-tls`,
+    /// `rustls-tls-native-roots`, or `rustls-tls-webpki-roots`) is selected at
+    /// compile time via the matching Cargo feature on this crate, per
+    /// deployment (see `Cargo.toml`).
+    pub fn http_client(&self) -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_millis(
+                self.http_connect_timeout_ms,
+            ))
+            .timeout(std::time::Duration::from_millis(
+                self.http_request_timeout_ms,
+            ))
+            .build()
+    }
+
     pub fn bind_address(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
@@ -28,6 +185,33 @@ impl Config {
     pub fn server_url(&self) -> String {
         format!("http://{}:{}", self.host, self.port)
     }
+
+    /// Build a `tower_http::CompressionLayer` enabling the algorithms named
+    /// in `compression_algorithms` (any of `gzip`, `br`, `zstd`) and
+    /// negotiating via the client's `Accept-Encoding`, skipping bodies
+    /// smaller than `compression_min_size_bytes`.
+    pub fn compression_layer(&self) -> tower_http::compression::CompressionLayer {
+        let mut layer = tower_http::compression::CompressionLayer::new()
+            .gzip(false)
+            .br(false)
+            .zstd(false);
+
+        for algorithm in &self.compression_algorithms {
+            layer = match algorithm.as_str() {
+                "gzip" => layer.gzip(true),
+                "br" => layer.br(true),
+                "zstd" => layer.zstd(true),
+                other => {
+                    tracing::warn!("Ignoring unknown compression algorithm '{}'", other);
+                    layer
+                }
+            };
+        }
+
+        layer.compress_when(tower_http::compression::predicate::SizeAbove::new(
+            self.compression_min_size_bytes,
+        ))
+    }
 }
 
 impl Default for Config {
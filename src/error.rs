@@ -12,6 +12,10 @@ pub enum AppError {
     BadRequest(String),
     InternalServerError(String),
     ValidationError(String),
+    /// A configured HTTP timeout (connect, request, or workflow deadline) elapsed.
+    Timeout(String),
+    /// A per-client rate limit was exceeded.
+    RateLimited(String),
 }
 
 /// Error response structure
@@ -26,6 +30,8 @@ impl IntoResponse for AppError {
         let (status, error_type, message) = match self {
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg),
             AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg),
+            AppError::Timeout(msg) => (StatusCode::GATEWAY_TIMEOUT, "TIMEOUT", msg),
+            AppError::RateLimited(msg) => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED", msg),
             AppError::InternalServerError(msg) => {
                 error!("Internal server error: {}", msg);
                 (
@@ -57,5 +63,44 @@ impl From<String> for AppError {
     }
 }
 
+impl AppError {
+    /// Maps an `anyhow::Error` to `AppError::Timeout` when it was caused by a
+    /// `reqwest` connect/request timeout, otherwise to `InternalServerError`.
+    pub fn from_anyhow(context: &str, err: anyhow::Error) -> Self {
+        if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+            if reqwest_err.is_timeout() {
+                return AppError::Timeout(format!("{}: request timed out", context));
+            }
+        }
+        AppError::InternalServerError(format!("{}: {}", context, err))
+    }
+
+    /// Maps a `task_graph::GraphError` from workflow execution to
+    /// `AppError::Timeout` when its message indicates an underlying
+    /// `reqwest` timeout, otherwise to `InternalServerError`. Workflow tasks
+    /// (see `agent_workflow::data_retriever`) stringify their source error
+    /// into `GraphError::TaskExecutionFailed` before it can be downcast, so
+    /// this falls back to [`message_indicates_timeout`] instead of matching
+    /// on the error's type.
+    pub fn from_graph_error(context: &str, err: task_graph::GraphError) -> Self {
+        let message = err.to_string();
+        if message_indicates_timeout(&message) {
+            AppError::Timeout(format!("{}: request timed out", context))
+        } else {
+            AppError::InternalServerError(format!("{}: {}", context, message))
+        }
+    }
+}
+
+/// Whether an error message indicates the underlying call timed out - the
+/// one signal that survives once a workflow task has already stringified
+/// its source `anyhow::Error` into a `GraphError::TaskExecutionFailed`.
+/// Used by both `AppError::from_graph_error` and the job-queue worker
+/// (`jobs::run_job`), so a hung upstream is reported as a timeout in both
+/// the synchronous and background-job paths.
+pub fn message_indicates_timeout(message: &str) -> bool {
+    message.to_lowercase().contains("timed out")
+}
+
 /// Result type for application handlers
 pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,145 @@
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context as GqlContext, Object, Schema, SimpleObject, Upload};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::Extension;
+use axum::response::{Html, IntoResponse};
+use task_graph::ContextExt;
+use tracing::info;
+
+use crate::agent_workflow::{IngestedDocumentStore, context_vars, create_agent_workflow};
+use crate::scraper::WebScraper;
+
+/// The document a client uploads to bypass the web-search stage and feed the
+/// agent workflow directly from private content. `id` is passed to `query`'s
+/// `document_ids` argument to use this document as the retrieval source.
+#[derive(SimpleObject)]
+pub struct IngestedDocument {
+    pub id: String,
+    pub filename: String,
+    pub length: usize,
+}
+
+pub type AppSchema = Schema<QueryRoot, MutationRoot, async_graphql::EmptySubscription>;
+
+/// Build the schema, wiring in the shared [`WebScraper`] (the same instance
+/// `ScraperSingleton` hands to the REST API, cloned in by the caller) so the
+/// `scrape` field reuses the same browser instance as the REST API.
+pub fn build_schema(scraper: WebScraper) -> AppSchema {
+    Schema::build(QueryRoot, MutationRoot, async_graphql::EmptySubscription)
+        .data(scraper)
+        .finish()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Mirrors `GET /health`.
+    async fn health(&self) -> &'static str {
+        "ok"
+    }
+
+    /// Mirrors `POST /api/agent1`: runs the full agent workflow and returns
+    /// the answer. When `document_ids` (from `uploadDocument`) is non-empty,
+    /// the workflow uses those documents as its retrieval source instead of
+    /// running a live web search.
+    async fn query(
+        &self,
+        question: String,
+        document_ids: Option<Vec<String>>,
+    ) -> async_graphql::Result<String> {
+        let graph = create_agent_workflow(question)?;
+        if let Some(document_ids) = document_ids {
+            graph
+                .context()
+                .set(context_vars::INGESTED_DOCUMENT_IDS, document_ids)
+                .await;
+        }
+        graph
+            .execute()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let answer: String = graph
+            .context()
+            .get(context_vars::ANSWER)
+            .await
+            .ok_or_else(|| async_graphql::Error::new("Failed to retrieve answer from context"))?;
+
+        Ok(answer)
+    }
+
+    /// Mirrors `POST /v1/scrape`: fetches and cleans the text content of `url`.
+    async fn scrape(&self, ctx: &GqlContext<'_>, url: String) -> async_graphql::Result<String> {
+        let scraper = ctx.data::<WebScraper>()?;
+        let text = scraper.scrape_text(&url).await?;
+        Ok(text)
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Uploads an ad-hoc HTML/text document so it can be injected into the
+    /// workflow context as an additional retrieval source, bypassing the
+    /// web-search stage entirely. Implements the GraphQL multipart upload
+    /// spec. PDFs are rejected up front (see below) rather than ingested as
+    /// raw, unreadable bytes.
+    async fn upload_document(
+        &self,
+        file: Upload,
+        _ctx: &GqlContext<'_>,
+    ) -> async_graphql::Result<IngestedDocument> {
+        let upload = file.value(_ctx)?;
+        let filename = upload.filename.clone();
+        let content_type = upload.content_type.clone();
+
+        // PDFs are binary and not valid UTF-8, so `read_to_string` below
+        // would just fail opaquely for every real PDF. Until this ingests
+        // extracted text from PDFs, reject them explicitly instead.
+        let looks_like_pdf = content_type.as_deref() == Some("application/pdf")
+            || filename.to_lowercase().ends_with(".pdf");
+        if looks_like_pdf {
+            return Err(async_graphql::Error::new(
+                "PDF uploads are not yet supported; upload extracted text or HTML instead",
+            ));
+        }
+
+        let mut content = String::new();
+        use std::io::Read;
+        upload.into_read().read_to_string(&mut content).map_err(|e| {
+            async_graphql::Error::new(format!(
+                "Failed to read '{}' as text/HTML: {}",
+                filename, e
+            ))
+        })?;
+
+        let length = content.len();
+        let id = IngestedDocumentStore::insert(content);
+
+        info!(
+            "Ingested uploaded document '{}' as id '{}' ({} bytes)",
+            filename, id, length
+        );
+
+        Ok(IngestedDocument {
+            id,
+            filename,
+            length,
+        })
+    }
+}
+
+/// Serves the GraphiQL playground at `GET /graphql`.
+pub async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Handles `POST /graphql` requests against the schema.
+pub async fn graphql_handler(
+    Extension(schema): Extension<AppSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
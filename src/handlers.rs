@@ -1,8 +1,10 @@
 use crate::agent_workflow::{context_vars, create_agent_workflow};
+use crate::config::Config;
 use crate::error::{AppError, AppResult};
+use crate::jobs::JobQueue;
 use crate::models::{AgentRequest, AgentResponse, HealthResponse};
 use crate::scraper::WebScraper;
-use axum::{Extension, extract::Json, response::Json as ResponseJson};
+use axum::{Extension, extract::Json, extract::Path, response::Json as ResponseJson};
 use serde::{Deserialize, Serialize};
 use task_graph::ContextExt;
 use tracing::{debug, info};
@@ -11,6 +13,8 @@ use tracing::{debug, info};
 /// Returns the service status and health information
 pub async fn health_check() -> AppResult<ResponseJson<HealthResponse>> {
     debug!("Health check endpoint called");
+    crate::metrics::record_request("health_check");
+    let _timer = crate::metrics::Timer::start("health_check");
 
     let response = HealthResponse::ok();
 
@@ -24,6 +28,8 @@ pub async fn agent_handler(
     Json(payload): Json<AgentRequest>,
 ) -> AppResult<ResponseJson<AgentResponse>> {
     info!("Agent endpoint called with query: {}", payload.query);
+    crate::metrics::record_request("agent_handler");
+    let _timer = crate::metrics::Timer::start("agent_handler");
 
     // Validate the request
     if !payload.is_valid() {
@@ -38,11 +44,15 @@ pub async fn agent_handler(
     }
     let graph = graph.unwrap();
 
-    // run the workflow
-    graph
-        .execute()
+    // run the workflow, bounded by an overall deadline so a hung step (e.g.
+    // headless-Chrome scraping, which isn't covered by reqwest's own
+    // timeouts) can't pin a worker forever
+    let workflow_timeout =
+        std::time::Duration::from_millis(Config::from_env().workflow_timeout_ms);
+    tokio::time::timeout(workflow_timeout, graph.execute())
         .await
-        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        .map_err(|_| AppError::Timeout("Agent workflow: request timed out".to_string()))?
+        .map_err(|e| AppError::from_graph_error("Agent workflow failed", e))?;
 
     let answer: String = graph
         .context()
@@ -57,6 +67,51 @@ pub async fn agent_handler(
     Ok(ResponseJson(response))
 }
 
+#[derive(Debug, Serialize)]
+pub struct EnqueueJobResponse {
+    pub job_id: String,
+    pub status: &'static str,
+}
+
+/// Enqueues an agent query for background processing and returns immediately
+/// with a job id that can be polled via `GET /v1/agent/{job_id}`.
+pub async fn enqueue_agent_job(
+    Extension(job_queue): Extension<JobQueue>,
+    Json(payload): Json<AgentRequest>,
+) -> AppResult<ResponseJson<EnqueueJobResponse>> {
+    info!("Enqueueing agent job for query: {}", payload.query);
+
+    if !payload.is_valid() {
+        return Err(AppError::ValidationError(
+            "Query cannot be empty or only whitespace".to_string(),
+        ));
+    }
+
+    let job_id = job_queue
+        .enqueue(payload.query)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(ResponseJson(EnqueueJobResponse {
+        job_id,
+        status: "queued",
+    }))
+}
+
+/// Returns the current status (and answer, once available) of a previously
+/// enqueued agent job.
+pub async fn get_agent_job(
+    Extension(job_queue): Extension<JobQueue>,
+    Path(job_id): Path<String>,
+) -> AppResult<ResponseJson<crate::jobs::JobStatus>> {
+    let status = job_queue
+        .status(&job_id)
+        .await
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown job id: {}", job_id)))?;
+
+    Ok(ResponseJson(status))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ScrapeRequest {
     pub url: String,
@@ -76,6 +131,8 @@ pub async fn scrape_handler(
     Json(payload): Json<ScrapeRequest>,
 ) -> AppResult<ResponseJson<ScrapeResponse>> {
     info!("Scrape endpoint called for URL: {}", payload.url);
+    crate::metrics::record_request("scrape_handler");
+    let _timer = crate::metrics::Timer::start("scrape_handler");
 
     // Validate URL
     if payload.url.trim().is_empty() {
@@ -86,7 +143,7 @@ pub async fn scrape_handler(
     let text = scraper
         .scrape_text(&payload.url)
         .await
-        .map_err(|e| AppError::InternalServerError(format!("Scraping failed: {}", e)))?;
+        .map_err(|e| AppError::from_anyhow("Scraping failed", e))?;
 
     let response = ScrapeResponse {
         url: payload.url,
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::agent_workflow::{context_vars, create_agent_workflow};
+use crate::config::Config;
+use task_graph::ContextExt;
+
+/// Status of a background agent job, as reported to clients polling `GET /v1/agent/{job_id}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done { answer: String },
+    /// `timeout` is set when `error` indicates the underlying call timed
+    /// out (see `error::message_indicates_timeout`), so a hung upstream is
+    /// distinguishable from any other workflow failure without the client
+    /// having to parse `error`.
+    Failed { error: String, timeout: bool },
+}
+
+/// Pluggable storage for job state, so the in-memory default can later be
+/// swapped for a Redis-backed implementation without touching the workers.
+#[async_trait::async_trait]
+pub trait JobStore: Send + Sync {
+    async fn set(&self, job_id: &str, status: JobStatus);
+    async fn get(&self, job_id: &str) -> Option<JobStatus>;
+}
+
+/// Default in-process job store backed by `DashMap`.
+#[derive(Clone, Default)]
+pub struct InMemoryJobStore {
+    jobs: Arc<DashMap<String, JobStatus>>,
+}
+
+impl InMemoryJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn set(&self, job_id: &str, status: JobStatus) {
+        self.jobs.insert(job_id.to_string(), status);
+    }
+
+    async fn get(&self, job_id: &str) -> Option<JobStatus> {
+        self.jobs.get(job_id).map(|entry| entry.clone())
+    }
+}
+
+/// A queued request for the agent workflow, identified by `job_id`.
+struct AgentJob {
+    job_id: String,
+    query: String,
+}
+
+/// Shared handle used by handlers to enqueue jobs and the worker pool to
+/// drain them.
+#[derive(Clone)]
+pub struct JobQueue {
+    store: Arc<dyn JobStore>,
+    sender: mpsc::Sender<AgentJob>,
+}
+
+impl JobQueue {
+    /// Spawn `worker_concurrency` workers draining the queue and return a
+    /// handle for enqueuing new jobs.
+    pub fn spawn(worker_concurrency: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(1024);
+        let store: Arc<dyn JobStore> = Arc::new(InMemoryJobStore::new());
+        let receiver = Arc::new(tokio::sync::Mutex::new(receiver));
+
+        for worker_id in 0..worker_concurrency.max(1) {
+            let receiver = receiver.clone();
+            let store = store.clone();
+            tokio::spawn(async move {
+                info!("Agent worker {} started", worker_id);
+                loop {
+                    let job = {
+                        let mut receiver = receiver.lock().await;
+                        receiver.recv().await
+                    };
+                    let Some(job) = job else {
+                        break;
+                    };
+                    run_job(&store, job).await;
+                }
+                info!("Agent worker {} stopped", worker_id);
+            });
+        }
+
+        Self { store, sender }
+    }
+
+    /// Enqueue `query` for processing and return its generated job id.
+    pub async fn enqueue(&self, query: String) -> anyhow::Result<String> {
+        let job_id = Uuid::new_v4().to_string();
+        self.store.set(&job_id, JobStatus::Queued).await;
+        self.sender
+            .send(AgentJob {
+                job_id: job_id.clone(),
+                query,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Agent job queue is closed"))?;
+        Ok(job_id)
+    }
+
+    /// Look up the current status of a previously enqueued job.
+    pub async fn status(&self, job_id: &str) -> Option<JobStatus> {
+        self.store.get(job_id).await
+    }
+}
+
+async fn run_job(store: &Arc<dyn JobStore>, job: AgentJob) {
+    store.set(&job.job_id, JobStatus::Running).await;
+
+    let result: anyhow::Result<String> = async {
+        let graph = create_agent_workflow(job.query.clone())?;
+        let workflow_timeout =
+            std::time::Duration::from_millis(Config::from_env().workflow_timeout_ms);
+        tokio::time::timeout(workflow_timeout, graph.execute())
+            .await
+            .map_err(|_| anyhow::anyhow!("Agent workflow: request timed out"))?
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let answer: String = graph
+            .context()
+            .get(context_vars::ANSWER)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve answer from context"))?;
+        Ok(answer)
+    }
+    .await;
+
+    match result {
+        Ok(answer) => {
+            info!("Job {} completed", job.job_id);
+            store.set(&job.job_id, JobStatus::Done { answer }).await;
+        }
+        Err(e) => {
+            error!("Job {} failed: {}", job.job_id, e);
+            let error = e.to_string();
+            let timeout = crate::error::message_indicates_timeout(&error);
+            store
+                .set(&job.job_id, JobStatus::Failed { error, timeout })
+                .await;
+        }
+    }
+}
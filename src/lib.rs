@@ -1,10 +1,18 @@
 pub mod app;
+pub mod auth;
+pub mod cache;
 pub mod config;
 pub mod error;
+pub mod graphql;
 pub mod handlers;
+pub mod jobs;
+pub mod metrics;
 pub mod models;
+pub mod rate_limit;
 pub mod routes;
 pub mod scraper;
+pub mod user_agent;
+pub mod webdriver_scraper;
 
 // Re-export key functions for convenience
 pub mod agent_workflow;
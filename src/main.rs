@@ -1,5 +1,6 @@
 use rs_rag_svc::app::{create_app, init_tracing};
 use rs_rag_svc::config::Config;
+use std::net::SocketAddr;
 use tracing::{error, info};
 
 #[tokio::main]
@@ -28,6 +29,7 @@ async fn main() {
             info!("Server running on {}", config.server_url());
             info!("Health check: GET /health");
             info!("Agent endpoint: POST /api/agent1");
+            info!("Background agent job: POST /v1/agent, GET /v1/agent/{{job_id}}");
             listener
         }
         Err(e) => {
@@ -38,7 +40,14 @@ async fn main() {
 
     // Start the server
     info!("Server starting...");
-    if let Err(e) = axum::serve(listener, app).await {
+    // Connect info is recorded so the rate limiter can key off client IP
+    // when no `X-API-Key` header is present.
+    if let Err(e) = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    {
         error!("Server error: {}", e);
     } else {
         info!("Server shutdown gracefully");
@@ -0,0 +1,79 @@
+use axum::response::IntoResponse;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::time::Instant;
+
+/// Installs the global Prometheus recorder and returns a handle that can
+/// render the current metrics snapshot for the `/metrics` route.
+///
+/// Should be called once, before any `metrics::counter!`/`histogram!` calls,
+/// typically alongside [`crate::app::init_tracing`].
+pub fn install_recorder() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("Failed to install Prometheus metrics recorder")
+}
+
+/// Handler for `GET /metrics`, rendering the Prometheus text exposition format.
+pub async fn metrics_handler(
+    axum::extract::Extension(handle): axum::extract::Extension<PrometheusHandle>,
+) -> impl IntoResponse {
+    handle.render()
+}
+
+/// RAII guard that records a histogram sample (in seconds) and an in-flight
+/// gauge for a named operation when dropped, regardless of success/failure.
+pub struct Timer {
+    name: &'static str,
+    started_at: Instant,
+}
+
+impl Timer {
+    pub fn start(name: &'static str) -> Self {
+        metrics::gauge!("in_flight_requests", "handler" => name).increment(1.0);
+        Self {
+            name,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        metrics::histogram!("request_duration_seconds", "handler" => self.name).record(elapsed);
+        metrics::gauge!("in_flight_requests", "handler" => self.name).decrement(1.0);
+    }
+}
+
+/// Increment the total-requests counter for `handler`.
+pub fn record_request(handler: &'static str) {
+    metrics::counter!("requests_total", "handler" => handler).increment(1);
+}
+
+/// Record a single search-latency sample, in seconds.
+pub fn record_search_latency(seconds: f64) {
+    metrics::histogram!("search_duration_seconds").record(seconds);
+}
+
+/// Record a single per-URL scrape-latency sample, in seconds.
+pub fn record_scrape_latency(seconds: f64) {
+    metrics::histogram!("scrape_duration_seconds").record(seconds);
+}
+
+/// Increment the counter tracking whether a discovered URL was scrapeable or skipped.
+pub fn record_url_filtered(scrapeable: bool) {
+    let label = if scrapeable { "scrapeable" } else { "skipped" };
+    metrics::counter!("search_urls_total", "outcome" => label).increment(1);
+}
+
+/// Increment the counter tracking scraped pages dropped for being too short.
+pub fn record_page_dropped() {
+    metrics::counter!("scraped_pages_dropped_total").increment(1);
+}
+
+/// Increment the counter tracking whether a request was allowed or rejected
+/// by the per-client rate limiter.
+pub fn record_rate_limit_outcome(allowed: bool) {
+    let label = if allowed { "allowed" } else { "rejected" };
+    metrics::counter!("rate_limit_requests_total", "outcome" => label).increment(1);
+}
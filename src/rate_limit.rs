@@ -0,0 +1,106 @@
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::warn;
+
+use crate::cache::{Cache, CacheExt, cache_key, shared_cache};
+use crate::config::Config;
+use crate::error::AppError;
+
+/// Sliding-window per-client rate limiter backed by the same pluggable
+/// [`Cache`] used for search/answer memoization, so counters stay accurate
+/// across multiple service instances when `REDIS_URL` is configured.
+pub struct RateLimiter {
+    cache: std::sync::Arc<dyn Cache>,
+    window: Duration,
+    max_requests: u32,
+}
+
+impl RateLimiter {
+    pub fn new(cache: std::sync::Arc<dyn Cache>, window: Duration, max_requests: u32) -> Self {
+        Self {
+            cache,
+            window,
+            max_requests,
+        }
+    }
+
+    /// Records a request for `client_key` and reports whether it falls
+    /// within the allowed rate, dropping timestamps that have aged out of
+    /// the sliding window.
+    async fn check(&self, client_key: &str) -> anyhow::Result<bool> {
+        let key = cache_key("ratelimit", client_key);
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let window_start = now_ms.saturating_sub(self.window.as_millis() as u64);
+
+        let mut timestamps: Vec<u64> = self.cache.get(&key).await?.unwrap_or_default();
+        timestamps.retain(|&t| t > window_start);
+
+        if timestamps.len() as u32 >= self.max_requests {
+            return Ok(false);
+        }
+
+        timestamps.push(now_ms);
+        self.cache.set(&key, &timestamps, self.window).await?;
+        Ok(true)
+    }
+}
+
+/// Identifies the caller for rate-limiting purposes: the `X-API-Key` header
+/// when present, otherwise the connecting peer's IP address.
+fn client_key(request: &Request) -> String {
+    request
+        .headers()
+        .get("X-API-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ci| ci.0.ip().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Tower/axum middleware enforcing a sliding-window request limit per client
+/// on `/api/agent1`, since each call spends LLM and search-API quota. Window
+/// size and request cap come from `Config::rate_limit_window_seconds` /
+/// `Config::rate_limit_max_requests`. Fails open (lets the request through)
+/// if the backing store can't be reached, so a cache outage doesn't take
+/// down the whole service.
+pub async fn rate_limit_middleware(request: Request, next: Next) -> Response {
+    let config = Config::from_env();
+    let cache = shared_cache(config.redis_url.as_deref());
+    let limiter = RateLimiter::new(
+        cache,
+        Duration::from_secs(config.rate_limit_window_seconds),
+        config.rate_limit_max_requests,
+    );
+
+    let client_key = client_key(&request);
+
+    match limiter.check(&client_key).await {
+        Ok(true) => {
+            crate::metrics::record_rate_limit_outcome(true);
+            next.run(request).await
+        }
+        Ok(false) => {
+            crate::metrics::record_rate_limit_outcome(false);
+            AppError::RateLimited(format!(
+                "Rate limit exceeded: max {} requests per {:?}",
+                config.rate_limit_max_requests, limiter.window
+            ))
+            .into_response()
+        }
+        Err(e) => {
+            warn!("Rate limiter store unavailable, allowing request: {}", e);
+            next.run(request).await
+        }
+    }
+}
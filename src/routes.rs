@@ -1,9 +1,37 @@
-use crate::handlers::{agent_handler, health_check};
-use axum::{Router, routing::get, routing::post};
+use crate::auth::{require_api_key, unknown_api_version};
+use crate::handlers::{
+    agent_handler, enqueue_agent_job, get_agent_job, health_check, scrape_handler,
+};
+use crate::rate_limit::rate_limit_middleware;
+use axum::{Router, middleware, routing::any, routing::get, routing::post};
+
+/// Versioned routes that require a valid API key: the scraper and
+/// background-agent surface, mounted under `/v1/...` so the API can evolve
+/// without breaking clients pinned to an older version.
+fn create_v1_routes() -> Router {
+    Router::new()
+        .route("/scrape", post(scrape_handler))
+        .route("/agent", post(enqueue_agent_job))
+        .route("/agent/{job_id}", get(get_agent_job))
+        .layer(middleware::from_fn(require_api_key))
+}
+
+/// The synchronous agent endpoint, rate limited per client since each call
+/// spends LLM and search-API quota.
+fn create_agent1_routes() -> Router {
+    Router::new()
+        .route("/api/agent1", post(agent_handler))
+        .layer(middleware::from_fn(rate_limit_middleware))
+}
 
 /// Creates and configures all application routes
 pub fn create_routes() -> Router {
     Router::new()
         .route("/health", get(health_check))
-        .route("/api/agent1", post(agent_handler))
+        .merge(create_agent1_routes())
+        .nest("/v1", create_v1_routes())
+        // Any other "/{version}/..." path is rejected explicitly rather than
+        // falling through to a generic 404, so clients on an unsupported
+        // version get a clear signal.
+        .route("/{version}/{*rest}", any(unknown_api_version))
 }
@@ -1,23 +1,438 @@
 use anyhow::{Context, Result, anyhow};
-use headless_chrome::{Browser, LaunchOptionsBuilder};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use headless_chrome::protocol::cdp::Fetch::events::RequestPausedEvent;
+use headless_chrome::protocol::cdp::Fetch::{RequestPattern, RequestStage};
+use headless_chrome::protocol::cdp::Network::ErrorReason;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use headless_chrome::protocol::cdp::Runtime::events::{ConsoleAPICalledEvent, ExceptionThrownEvent};
+use headless_chrome::types::RequestPausedDecision;
+pub use headless_chrome::protocol::cdp::Network::ResourceType;
+use headless_chrome::{Browser, Event, LaunchOptionsBuilder};
+use rand::Rng;
 use scraper::{Html, Selector};
-use std::time::Duration;
-use tokio::sync::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore};
 use tokio::time::sleep;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
-/// Thread-safe web scraper optimized for server use
-/// Reuses a single browser instance across multiple async requests
+use crate::cache::{Cache, CacheExt, DiskCache, cache_key, shared_cache};
+use crate::config::Config;
+use crate::user_agent::random_user_agent;
+
+/// Options governing [`WebScraper::crawl`]'s breadth-first traversal.
+#[derive(Debug, Clone)]
+pub struct CrawlOptions {
+    /// Links are followed up to (but not including) this depth; the root is depth 0.
+    pub max_depth: usize,
+    /// Stop the crawl once this many pages have been scraped, regardless of remaining frontier.
+    pub max_pages: usize,
+    /// When true, only follow links whose host matches the root URL's host.
+    pub same_domain_only: bool,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            max_pages: 50,
+            same_domain_only: true,
+        }
+    }
+}
+
+/// One page collected by [`WebScraper::crawl`].
+#[derive(Debug, Clone)]
+pub struct ScrapedPage {
+    pub url: String,
+    pub text: String,
+}
+
+/// What [`WebScraper::scrape_with_captures`] should render alongside the
+/// extracted text. Both default to off so the plain text path (used by
+/// [`WebScraper::scrape_text`]) stays fast.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    /// Render a full-page screenshot in this format (`None` skips it).
+    pub screenshot_format: Option<CaptureScreenshotFormatOption>,
+    /// JPEG quality, 0-100; ignored for PNG and when `screenshot_format` is `None`.
+    pub screenshot_quality: Option<i64>,
+    /// Render the page to PDF.
+    pub capture_pdf: bool,
+}
+
+/// The outcome of [`WebScraper::scrape_with_captures`]: the extracted text,
+/// plus whichever rendered artifacts `CaptureOptions` asked for. `screenshot`/
+/// `pdf` are `None` when the corresponding option was left off.
+#[derive(Debug, Clone)]
+pub struct ScrapeCaptures {
+    pub text: String,
+    pub screenshot: Option<Vec<u8>>,
+    pub pdf: Option<Vec<u8>>,
+}
+
+/// Console/exception/network activity captured while
+/// [`WebScraper::scrape_with_diagnostics`] loads a page, so a silently-failed
+/// page or an empty render can be debugged directly instead of guessing from
+/// timing logs.
+#[derive(Debug, Clone, Default)]
+pub struct PageDiagnostics {
+    /// `console.*` calls, formatted as `"<level>: <args joined by space>"`.
+    pub console: Vec<String>,
+    /// Uncaught JS exceptions, formatted as their description/stack text.
+    pub exceptions: Vec<String>,
+    /// `(url, status)` for every response with a 4xx/5xx status.
+    pub failed_requests: Vec<(String, i64)>,
+}
+
+/// Parsed `robots.txt` rules for the `User-agent: *` group of one host, kept
+/// per-host by [`WebScraper`] so the file is fetched once and reused.
+#[derive(Debug, Clone, Default)]
+struct RobotsRules {
+    disallow: Vec<String>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    /// A path is allowed unless it starts with one of the recorded
+    /// `Disallow` prefixes (the standard robots.txt matching rule).
+    fn is_allowed(&self, path: &str) -> bool {
+        !self
+            .disallow
+            .iter()
+            .any(|prefix| !prefix.is_empty() && path.starts_with(prefix.as_str()))
+    }
+}
+
+/// The `deflect=<token>` cookie edge nodes grant after solving eQualit.ie's
+/// Deflect challenge; eQualit.ie documents it as valid for roughly 24 hours.
+const DEFLECT_COOKIE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A captured `deflect` cookie for one host, along with when it was captured
+/// so [`WebScraper::try_deflect_cookie_replay`] can tell it's gone stale.
+#[derive(Debug, Clone)]
+struct StoredDeflectCookie {
+    value: String,
+    captured_at: Instant,
+}
+
+impl StoredDeflectCookie {
+    fn is_expired(&self) -> bool {
+        self.captured_at.elapsed() >= DEFLECT_COOKIE_TTL
+    }
+}
+
+/// Whether `body` still carries the Deflect "verifying you are human" banner,
+/// meaning a cookie replay didn't bypass the challenge after all.
+fn is_challenge_banner(body: &str) -> bool {
+    let lower = body.to_lowercase();
+    lower.contains("challenge") || lower.contains("deflect") || lower.contains("verifying")
+}
+
+/// One pre-extraction interaction step to run against a [`ScraperBackend`]
+/// before reading back a page's HTML, for pages that only reveal content
+/// after a click, a filled-in form, or a search submission.
+#[derive(Debug, Clone)]
+pub enum Interaction {
+    /// Click the element matching this CSS selector.
+    Click(String),
+    /// Fill each `(selector, value)` pair into its matching form field.
+    FillForm(HashMap<String, String>),
+    /// Submit the form owning the last-interacted element.
+    Submit,
+    /// Wait until the element matching this CSS selector appears.
+    WaitForElement(String),
+    /// Focus the element matching this CSS selector.
+    Focus(String),
+    /// Type `.1` into the element matching the CSS selector `.0`.
+    TypeText(String, String),
+    /// Scroll the element matching this CSS selector into view.
+    ScrollTo(String),
+    /// Sleep for this many milliseconds, for pages that reveal content on a
+    /// timer (e.g. infinite scroll, a delayed modal).
+    Sleep(u64),
+}
+
+/// High-level interactive actions a scraping backend can perform before
+/// reading back a page's rendered HTML, driven by CSS selectors so callers
+/// can script "fill this box, submit, wait, then read" without caring which
+/// underlying driver is in play. Implemented by the per-call headless
+/// Chrome/CDP tab session that [`WebScraper`] drives its interaction steps
+/// through, and by [`crate::webdriver_scraper::WebDriverScraper`] (via an
+/// external WebDriver endpoint), for sites whose content only appears after
+/// a click, a consent dialog, or a submitted search form.
+#[async_trait]
+pub trait ScraperBackend: Send + Sync {
+    /// Click the element matching `selector`.
+    async fn click(&self, selector: &str) -> Result<()>;
+    /// Fill each `(selector, value)` pair into its matching form field.
+    async fn fill_form(&self, fields: &HashMap<String, String>) -> Result<()>;
+    /// Submit the form owning the last-interacted element.
+    async fn submit(&self) -> Result<()>;
+    /// Read back the current page's rendered HTML.
+    async fn source(&self) -> Result<String>;
+    /// Wait until the element matching `selector` appears.
+    async fn wait_for_element(&self, selector: &str) -> Result<()>;
+    /// Focus the element matching `selector`.
+    async fn focus(&self, selector: &str) -> Result<()>;
+    /// Type `text` into the element matching `selector`.
+    async fn type_text(&self, selector: &str, text: &str) -> Result<()>;
+    /// Scroll the element matching `selector` into view.
+    async fn scroll_to(&self, selector: &str) -> Result<()>;
+
+    /// Sleep for `ms` milliseconds. Backend-independent, so the default
+    /// covers every implementor.
+    async fn sleep(&self, ms: u64) -> Result<()> {
+        sleep(Duration::from_millis(ms)).await;
+        Ok(())
+    }
+
+    /// Run `steps` against this backend in order, dispatching each
+    /// [`Interaction`] variant to its matching method.
+    async fn run_interactions(&self, steps: &[Interaction]) -> Result<()> {
+        for step in steps {
+            match step {
+                Interaction::Click(selector) => self.click(selector).await?,
+                Interaction::FillForm(fields) => self.fill_form(fields).await?,
+                Interaction::Submit => self.submit().await?,
+                Interaction::WaitForElement(selector) => self.wait_for_element(selector).await?,
+                Interaction::Focus(selector) => self.focus(selector).await?,
+                Interaction::TypeText(selector, text) => self.type_text(selector, text).await?,
+                Interaction::ScrollTo(selector) => self.scroll_to(selector).await?,
+                Interaction::Sleep(ms) => self.sleep(*ms).await?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// [`ScraperBackend`] over a single headless Chrome tab, scoped to one
+/// [`WebScraper::navigate_and_extract`] call. `WebScraper` pools several
+/// browsers behind a round-robin index rather than holding one persistent
+/// session (see its doc comment), so interactions are driven through this
+/// thin per-call wrapper around the tab checked out of the pool for that call.
+struct ChromeTabSession<'a> {
+    tab: &'a headless_chrome::Tab,
+}
+
+#[async_trait]
+impl ScraperBackend for ChromeTabSession<'_> {
+    async fn click(&self, selector: &str) -> Result<()> {
+        self.tab
+            .wait_for_element(selector)
+            .with_context(|| format!("Element not found for click: {}", selector))?
+            .click()
+            .with_context(|| format!("Failed to click: {}", selector))?;
+        Ok(())
+    }
+
+    async fn fill_form(&self, fields: &HashMap<String, String>) -> Result<()> {
+        for (selector, value) in fields {
+            self.tab
+                .wait_for_element(selector)
+                .with_context(|| format!("Form field not found: {}", selector))?
+                .type_into(value)
+                .with_context(|| format!("Failed to fill field: {}", selector))?;
+        }
+        Ok(())
+    }
+
+    async fn submit(&self) -> Result<()> {
+        self.tab
+            .evaluate(
+                "document.activeElement && document.activeElement.form && document.activeElement.form.submit();",
+                false,
+            )
+            .context("Failed to submit form")?;
+        Ok(())
+    }
+
+    async fn source(&self) -> Result<String> {
+        self.tab.get_content().context("Failed to get page content")
+    }
+
+    async fn wait_for_element(&self, selector: &str) -> Result<()> {
+        self.tab
+            .wait_for_element(selector)
+            .with_context(|| format!("Element never appeared: {}", selector))?;
+        Ok(())
+    }
+
+    async fn focus(&self, selector: &str) -> Result<()> {
+        self.tab
+            .find_element(selector)
+            .with_context(|| format!("Element not found: {}", selector))?
+            .focus()
+            .with_context(|| format!("Failed to focus: {}", selector))?;
+        Ok(())
+    }
+
+    async fn type_text(&self, selector: &str, text: &str) -> Result<()> {
+        self.tab
+            .find_element(selector)
+            .with_context(|| format!("Element not found: {}", selector))?
+            .type_into(text)
+            .with_context(|| format!("Failed to type into: {}", selector))?;
+        Ok(())
+    }
+
+    async fn scroll_to(&self, selector: &str) -> Result<()> {
+        let script = format!(
+            "document.querySelector({:?})?.scrollIntoView({{behavior: 'instant', block: 'center'}})",
+            selector
+        );
+        self.tab
+            .evaluate(&script, false)
+            .with_context(|| format!("Failed to scroll to: {}", selector))?;
+        Ok(())
+    }
+}
+
+/// Thread-safe web scraper optimized for server use.
+///
+/// Holds a pool of long-lived browser instances instead of one shared
+/// `Mutex<Browser>`, so tab creation for one caller doesn't serialize
+/// behind every other concurrent scrape, and a crashed browser only takes
+/// its own slot in the pool offline rather than stalling the whole server.
+/// A `Semaphore` bounds how many scrapes run at once across the pool - every
+/// tab-opening path (`scrape_text`, the deflect cookie's browser fallback,
+/// `crawl`, `scrape_with_captures`, `scrape_with_diagnostics`) checks its tab
+/// out through [`Self::checkout_tab`], so they all share this same bound
+/// rather than each needing their own pool.
 #[derive(Clone)]
 pub struct WebScraper {
-    browser: std::sync::Arc<Mutex<Browser>>,
+    browsers: Arc<Vec<Mutex<Browser>>>,
+    permits: Arc<Semaphore>,
+    next_browser: Arc<AtomicUsize>,
+    /// `robots.txt` rules already fetched, keyed by host.
+    robots_cache: Arc<Mutex<HashMap<String, RobotsRules>>>,
+    /// Opt-in disk-backed cache for scraped text and its TTL, set via
+    /// [`Self::with_cache`]. When absent, `scrape_text` falls back to the
+    /// Redis/in-memory cache selected by `Config::redis_url`.
+    disk_cache: Option<(Arc<DiskCache>, Duration)>,
+    /// Per-instance User-Agent pool override, set via [`Self::with_user_agents`].
+    /// When absent, falls back to `Config::user_agents`/the built-in defaults
+    /// via [`random_user_agent`].
+    user_agents: Option<Vec<String>>,
+    /// Extra HTTP headers sent with navigations made by `scrape_text` and
+    /// `crawl`, set via [`Self::with_headers`].
+    extra_headers: Option<HashMap<String, String>>,
+    /// Captured `deflect` (eQualit.ie Deflect challenge) cookies keyed by
+    /// host, so a follow-up scrape of a host already solved this session can
+    /// skip the browser entirely via [`Self::try_deflect_cookie_replay`].
+    deflect_cookies: Arc<Mutex<HashMap<String, StoredDeflectCookie>>>,
+    /// Resource types (images, fonts, stylesheets, media, ...) to abort
+    /// outright via CDP `Fetch` interception, set via
+    /// [`Self::with_resource_blocking`]. Empty by default - interception is
+    /// an opt-in speedup rather than the default behavior of [`Self::new`].
+    block_resources: Vec<ResourceType>,
+    /// URL substrings (e.g. known ad/analytics hosts) to abort regardless of
+    /// resource type, set via [`Self::with_resource_blocking`].
+    block_url_substrings: Vec<String>,
 }
 
 impl WebScraper {
-    /// Create a new scraper with a long-lived browser instance
-    /// Call this once at server startup and clone/share the instance
+    /// Create a new scraper with a pool of long-lived browser instances,
+    /// sized by `Config::scraper_pool_size`, and a concurrency limit set by
+    /// `Config::scraper_max_concurrent`. Call this once at server startup
+    /// and clone/share the instance.
     pub async fn new() -> Result<Self> {
-        let browser = Browser::new(
+        let config = Config::from_env();
+        let pool_size = config.scraper_pool_size.max(1);
+
+        let mut browsers = Vec::with_capacity(pool_size);
+        for _ in 0..pool_size {
+            browsers.push(Mutex::new(Self::launch_browser()?));
+        }
+
+        Ok(Self {
+            browsers: Arc::new(browsers),
+            permits: Arc::new(Semaphore::new(config.scraper_max_concurrent.max(1))),
+            next_browser: Arc::new(AtomicUsize::new(0)),
+            robots_cache: Arc::new(Mutex::new(HashMap::new())),
+            disk_cache: None,
+            user_agents: None,
+            extra_headers: None,
+            deflect_cookies: Arc::new(Mutex::new(HashMap::new())),
+            block_resources: Vec::new(),
+            block_url_substrings: Vec::new(),
+        })
+    }
+
+    /// Abort any request whose resource type is in `resources` or whose URL
+    /// contains one of `url_substrings`, via CDP `Fetch` interception. Images,
+    /// fonts, and media are almost never needed to read a page's text, and
+    /// cutting them typically saves the bulk of a page's load time and
+    /// bandwidth; see [`ResourceType`] for the available types.
+    pub fn with_resource_blocking(
+        mut self,
+        resources: Vec<ResourceType>,
+        url_substrings: Vec<String>,
+    ) -> Self {
+        self.block_resources = resources;
+        self.block_url_substrings = url_substrings;
+        self
+    }
+
+    /// Override the User-Agent pool used for every navigation, replacing
+    /// `Config::user_agents`/the built-in defaults. Picked randomly per
+    /// scrape the same way [`random_user_agent`] does, so a caller targeting
+    /// one picky host can supply a pool known to work for it.
+    pub fn with_user_agents(mut self, user_agents: Vec<String>) -> Self {
+        self.user_agents = Some(user_agents);
+        self
+    }
+
+    /// Send `headers` with every navigation made by [`Self::scrape_text`],
+    /// [`Self::scrape_text_with_interactions`] and [`Self::crawl`] (e.g. a
+    /// site-specific `Accept-Language` or a referer a gated page expects),
+    /// in addition to the rotated User-Agent. [`Self::scrape_with_captures`]
+    /// and [`Self::scrape_with_diagnostics`] do not send them.
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.extra_headers = Some(headers);
+        self
+    }
+
+    /// Pick a User-Agent for one navigation: [`Self::with_user_agents`]'s
+    /// override when set, otherwise [`random_user_agent`]'s
+    /// `Config`/built-in default pool.
+    fn pick_user_agent(&self, config: &Config) -> String {
+        match &self.user_agents {
+            Some(pool) if !pool.is_empty() => {
+                let idx = rand::thread_rng().gen_range(0..pool.len());
+                pool[idx].clone()
+            }
+            _ => random_user_agent(config),
+        }
+    }
+
+    /// Opt into a disk-backed cache for scraped page text, keyed by
+    /// normalized URL, stored as JSON files under `dir` with entries
+    /// expiring after `ttl`. Scraping through headless Chrome is slow and
+    /// the same URLs recur across queries; this cuts both latency and
+    /// browser load for repeated questions, and survives process restarts
+    /// unlike the in-memory cache.
+    pub fn with_cache(mut self, dir: impl Into<std::path::PathBuf>, ttl: Duration) -> Result<Self> {
+        let disk_cache = DiskCache::new(dir).context("Failed to initialize disk cache")?;
+        self.disk_cache = Some((Arc::new(disk_cache), ttl));
+        Ok(self)
+    }
+
+    /// Remove `url`'s entry from the disk cache, if [`Self::with_cache`] was used.
+    pub async fn invalidate_cached(&self, url: &str) -> Result<()> {
+        if let Some((disk_cache, _)) = &self.disk_cache {
+            disk_cache.invalidate(&cache_key("scrape", url)).await?;
+        }
+        Ok(())
+    }
+
+    /// Launch one headless Chrome instance with the flags shared by every
+    /// browser in the pool.
+    fn launch_browser() -> Result<Browser> {
+        Browser::new(
             LaunchOptionsBuilder::default()
                 .headless(true)
                 .window_size(Some((1280, 800)))
@@ -29,21 +444,248 @@ impl WebScraper {
                 ])
                 .build()
                 .context("Invalid Chrome launch options")?,
-        )?;
-
-        Ok(Self {
-            browser: std::sync::Arc::new(Mutex::new(browser)),
-        })
+        )
+        .context("Failed to launch browser")
     }
 
     /// Scrape text from a URL - safe to call from multiple threads concurrently
     pub async fn scrape_text(&self, url: &str) -> Result<String> {
-        // Brief delay for politeness
-        sleep(Duration::from_millis(200)).await;
+        self.scrape_text_with_interactions(url, &[]).await
+    }
+
+    /// Like [`Self::scrape_text`], but first runs `steps` (clicks, form
+    /// fills, submissions) against the page before reading back its HTML -
+    /// for pages that only reveal content after an interaction, such as a
+    /// search form or a "load more" button. Bypasses the cache when `steps`
+    /// is non-empty, since an entry keyed only by URL would otherwise be
+    /// shared across different interaction scripts against the same page.
+    pub async fn scrape_text_with_interactions(
+        &self,
+        url: &str,
+        steps: &[Interaction],
+    ) -> Result<String> {
+        if steps.is_empty() {
+            let key = cache_key("scrape", url);
+
+            let (cache, ttl): (Arc<dyn Cache>, Duration) =
+                if let Some((disk_cache, ttl)) = &self.disk_cache {
+                    (disk_cache.clone() as Arc<dyn Cache>, *ttl)
+                } else {
+                    let config = Config::from_env();
+                    (
+                        shared_cache(config.redis_url.as_deref()),
+                        Duration::from_secs(config.cache_ttl_seconds),
+                    )
+                };
+
+            if let Some(cached) = cache.get::<String>(&key).await? {
+                info!("Cache hit for scrape URL: {}", url);
+                return Ok(cached);
+            }
+
+            let config = Config::from_env();
+            if let Some(text) = self.try_deflect_cookie_replay(url, &config).await {
+                cache.set(&key, &text, ttl).await?;
+                return Ok(text);
+            }
+
+            let (_, text) = self.navigate_and_extract(url, steps).await?;
+
+            cache.set(&key, &text, ttl).await?;
+
+            Ok(text)
+        } else {
+            let (_, text) = self.navigate_and_extract(url, steps).await?;
+            Ok(text)
+        }
+    }
+
+    /// Like [`Self::scrape_text`], but also renders a full-page screenshot
+    /// and/or a PDF of the final page when `captures` asks for them,
+    /// returning everything together in a [`ScrapeCaptures`]. Useful for
+    /// archiving/debugging pages where the extracted text looks wrong and
+    /// you need to see what the browser actually rendered. Bypasses the
+    /// cache, same as [`Self::scrape_text_with_interactions`] with non-empty
+    /// steps, since cached entries only ever hold text.
+    pub async fn scrape_with_captures(
+        &self,
+        url: &str,
+        captures: CaptureOptions,
+    ) -> Result<ScrapeCaptures> {
+        let config = Config::from_env();
+        let (_permit, tab) = self.checkout_tab(url, &config).await?;
+
+        let result = async {
+            if let Err(e) = tab.set_user_agent(&self.pick_user_agent(&config), None, None) {
+                warn!("Failed to set rotated User-Agent: {}", e);
+            }
+
+            if !self.block_resources.is_empty() || !self.block_url_substrings.is_empty() {
+                self.enable_interception(&tab)?;
+            }
+
+            tab.navigate_to(url)
+                .with_context(|| format!("Failed to navigate to {}", url))?;
+            self.wait_for_page_load(&tab).await?;
+
+            let html = tab.get_content().context("Failed to get page content")?;
+            let text = self.extract_text(&html);
+
+            let screenshot = match &captures.screenshot_format {
+                Some(format) => Some(
+                    tab.capture_screenshot(format.clone(), captures.screenshot_quality, None, true)
+                        .context("Failed to capture screenshot")?,
+                ),
+                None => None,
+            };
+
+            let pdf = if captures.capture_pdf {
+                Some(
+                    tab.print_to_pdf(None)
+                        .context("Failed to render page to PDF")?,
+                )
+            } else {
+                None
+            };
+
+            self.capture_deflect_cookie(&tab, url).await;
+
+            Ok(ScrapeCaptures {
+                text,
+                screenshot,
+                pdf,
+            })
+        }
+        .await;
+
+        let _ = tab.close_target();
+
+        result
+    }
+
+    /// Like [`Self::scrape_text`], but also subscribes to the tab's
+    /// console/exception/network events and returns them as
+    /// [`PageDiagnostics`] alongside the text. Bypasses the cache, same as
+    /// [`Self::scrape_with_captures`], since cached entries only ever hold text.
+    pub async fn scrape_with_diagnostics(&self, url: &str) -> Result<(String, PageDiagnostics)> {
+        let config = Config::from_env();
+        let (_permit, tab) = self.checkout_tab(url, &config).await?;
+
+        let result = async {
+            if let Err(e) = tab.set_user_agent(&self.pick_user_agent(&config), None, None) {
+                warn!("Failed to set rotated User-Agent: {}", e);
+            }
+
+            if !self.block_resources.is_empty() || !self.block_url_substrings.is_empty() {
+                self.enable_interception(&tab)?;
+            }
+
+            let diagnostics = Arc::new(std::sync::Mutex::new(PageDiagnostics::default()));
+
+            tab.enable_runtime()
+                .context("Failed to enable Runtime domain")?;
+
+            let listener_diagnostics = diagnostics.clone();
+            tab.add_event_listener(Arc::new(move |event: &Event| match event {
+                Event::RuntimeConsoleAPICalled(ConsoleAPICalledEvent { params, .. }) => {
+                    let message = params
+                        .args
+                        .iter()
+                        .filter_map(|arg| arg.value.as_ref().map(|v| v.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if let Ok(mut diagnostics) = listener_diagnostics.lock() {
+                        diagnostics
+                            .console
+                            .push(format!("{:?}: {}", params.call_type, message));
+                    }
+                }
+                Event::RuntimeExceptionThrown(ExceptionThrownEvent { params, .. }) => {
+                    let details = &params.exception_details;
+                    let message = details
+                        .exception
+                        .as_ref()
+                        .and_then(|exception| exception.description.clone())
+                        .unwrap_or_else(|| details.text.clone());
+                    if let Ok(mut diagnostics) = listener_diagnostics.lock() {
+                        diagnostics.exceptions.push(message);
+                    }
+                }
+                Event::NetworkResponseReceived(event) => {
+                    let status = event.params.response.status;
+                    if (400..600).contains(&status) {
+                        if let Ok(mut diagnostics) = listener_diagnostics.lock() {
+                            diagnostics
+                                .failed_requests
+                                .push((event.params.response.url.clone(), status));
+                        }
+                    }
+                }
+                _ => {}
+            }))
+            .context("Failed to subscribe to tab diagnostics events")?;
+
+            tab.navigate_to(url)
+                .with_context(|| format!("Failed to navigate to {}", url))?;
+            self.wait_for_page_load(&tab).await?;
+
+            let html = tab.get_content().context("Failed to get page content")?;
+            let text = self.extract_text(&html);
+
+            self.capture_deflect_cookie(&tab, url).await;
+
+            // The tab's event listener still holds a clone of the Arc, so
+            // take the collected contents out through the lock.
+            let diagnostics = diagnostics
+                .lock()
+                .map(|mut collected| std::mem::take(&mut *collected))
+                .unwrap_or_default();
+
+            Ok((text, diagnostics))
+        }
+        .await;
+
+        let _ = tab.close_target();
 
-        // Lock browser and create new tab (async-friendly mutex)
+        result
+    }
+
+    /// Apply the robots.txt politeness delay for `url` (if enabled), then
+    /// check out a tab from the browser pool, bounded by `self.permits`.
+    /// Shared by every tab-opening path ([`Self::navigate_and_extract`],
+    /// [`Self::scrape_with_captures`], [`Self::scrape_with_diagnostics`]) so
+    /// they all go through the same pool and the same politeness delay.
+    async fn checkout_tab(
+        &self,
+        url: &str,
+        config: &Config,
+    ) -> Result<(tokio::sync::SemaphorePermit<'_>, Arc<headless_chrome::Tab>)> {
+        let politeness_delay = if config.respect_robots_txt {
+            let rules = self.robots_rules_for(url).await?;
+            if !rules.is_allowed(&url_path(url)) {
+                return Err(anyhow!("URL disallowed by robots.txt: {}", url));
+            }
+            rules.crawl_delay.unwrap_or(Duration::from_millis(200))
+        } else {
+            Duration::from_millis(200)
+        };
+        sleep(politeness_delay).await;
+
+        // Bound overall concurrency across the pool before touching any browser.
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| anyhow!("Scraper semaphore closed: {}", e))?;
+
+        // Round-robin across the pool so load spreads evenly instead of
+        // hammering a single browser.
+        let index = self.next_browser.fetch_add(1, Ordering::Relaxed) % self.browsers.len();
+
+        // Lock only the chosen browser (async-friendly mutex); other slots
+        // remain free for concurrent callers.
         let tab = {
-            let mut browser = self.browser.lock().await;
+            let mut browser = self.browsers[index].lock().await;
 
             // Check if browser process is still alive
             if let Some(pid) = browser.get_process_id() {
@@ -52,32 +694,21 @@ impl WebScraper {
                 warn!("Browser process ID not available - might be a remote connection");
             }
 
-            // Try to create a tab, and if it fails, try to recreate the browser
+            // Try to create a tab, and if it fails, recreate only this
+            // browser - the rest of the pool keeps serving traffic.
             match browser.new_tab() {
                 Ok(tab) => tab,
                 Err(e) => {
                     warn!(
-                        "Failed to create tab, attempting to recreate browser: {}",
-                        e
+                        "Failed to create tab on pool slot {}, attempting to recreate browser: {}",
+                        index, e
                     );
 
-                    // Try to create a new browser instance
-                    let new_browser = Browser::new(
-                        LaunchOptionsBuilder::default()
-                            .headless(true)
-                            .window_size(Some((1280, 800)))
-                            .args(vec![
-                                std::ffi::OsStr::new("--disable-blink-features=AutomationControlled"),
-                                std::ffi::OsStr::new("--disable-web-security"),
-                                std::ffi::OsStr::new("--disable-features=VizDisplayCompositor"),
-                                std::ffi::OsStr::new("--user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36"),
-                            ])
-                            .build()
-                            .context("Invalid Chrome launch options")?,
-                    ).context("Failed to recreate browser")?;
+                    let new_browser =
+                        Self::launch_browser().context("Failed to recreate browser")?;
 
                     *browser = new_browser;
-                    debug!("Browser recreated successfully");
+                    debug!("Browser at pool slot {} recreated successfully", index);
 
                     browser
                         .new_tab()
@@ -86,8 +717,47 @@ impl WebScraper {
             }
         }; // Lock is released here
 
+        Ok((permit, tab))
+    }
+
+    /// Perform the actual navigate-wait-extract flow, bypassing the cache,
+    /// returning the raw page HTML alongside its cleaned text so callers
+    /// that need to look at the markup too (e.g. [`Self::crawl`]'s link
+    /// discovery) don't have to navigate a second time. Runs `steps` against
+    /// the page (via [`ChromeTabSession`]) right after the page load settles
+    /// and before extraction, so scripted interactions see the fully loaded DOM.
+    async fn navigate_and_extract(
+        &self,
+        url: &str,
+        steps: &[Interaction],
+    ) -> Result<(String, String)> {
+        let config = Config::from_env();
+        let (_permit, tab) = self.checkout_tab(url, &config).await?;
+
         // Perform scraping operations and ensure tab cleanup
         let result = async {
+            // Rotate the User-Agent per request so a fixed UA baked into the
+            // browser launch flags doesn't become a fingerprinting liability.
+            if let Err(e) = tab.set_user_agent(&self.pick_user_agent(&config), None, None) {
+                warn!("Failed to set rotated User-Agent: {}", e);
+            }
+
+            // Send any caller-supplied extra headers (e.g. Accept-Language,
+            // a referer a gated page expects) with the navigation.
+            if let Some(headers) = &self.extra_headers {
+                let headers: HashMap<&str, &str> = headers
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                if let Err(e) = tab.set_extra_http_headers(headers) {
+                    warn!("Failed to set extra HTTP headers: {}", e);
+                }
+            }
+
+            if !self.block_resources.is_empty() || !self.block_url_substrings.is_empty() {
+                self.enable_interception(&tab)?;
+            }
+
             // Navigate to URL
             tab.navigate_to(url)
                 .with_context(|| format!("Failed to navigate to {}", url))?;
@@ -95,11 +765,22 @@ impl WebScraper {
             // Wait for page load
             self.wait_for_page_load(&tab).await?;
 
+            // Run any scripted interactions (clicks, form fills, submits)
+            // before reading back the page, so content gated behind them
+            // is present in the extracted HTML.
+            if !steps.is_empty() {
+                let session = ChromeTabSession { tab: &tab };
+                session.run_interactions(steps).await?;
+                self.wait_for_page_load(&tab).await?;
+            }
+
             // Extract content
             let html = tab.get_content().context("Failed to get page content")?;
             let text = self.extract_text(&html);
 
-            Ok(text)
+            self.capture_deflect_cookie(&tab, url).await;
+
+            Ok((html, text))
         }
         .await;
 
@@ -109,6 +790,114 @@ impl WebScraper {
         result
     }
 
+    /// Enable CDP `Fetch`-domain interception on `tab`, aborting any request
+    /// that matches `self.block_resources`/`self.block_url_substrings` and
+    /// letting everything else (including the deflect challenge's own
+    /// script) through unmodified.
+    fn enable_interception(&self, tab: &headless_chrome::Tab) -> Result<()> {
+        let patterns = vec![RequestPattern {
+            url_pattern: Some("*".to_string()),
+            resource_type: None,
+            request_stage: Some(RequestStage::Request),
+        }];
+
+        let block_resources = self.block_resources.clone();
+        let block_substrings = self.block_url_substrings.clone();
+
+        tab.enable_request_interception(
+            &patterns,
+            Arc::new(move |_transport, _session_id, event: RequestPausedEvent| {
+                let request = &event.params.request;
+                let resource_type = event.params.resource_Type.clone();
+
+                let blocked_by_type = resource_type
+                    .map(|rt| block_resources.contains(&rt))
+                    .unwrap_or(false);
+                let blocked_by_url = block_substrings
+                    .iter()
+                    .any(|pattern| request.url.contains(pattern.as_str()));
+
+                if blocked_by_type || blocked_by_url {
+                    RequestPausedDecision::Fail(ErrorReason::BlockedByClient)
+                } else {
+                    RequestPausedDecision::Continue(None)
+                }
+            }),
+        )
+        .context("Failed to enable request interception")?;
+
+        Ok(())
+    }
+
+    /// Pull the `deflect` cookie for `url`'s host via CDP `Network.getCookies`
+    /// and stash it, so a follow-up scrape of the same host can try
+    /// [`Self::try_deflect_cookie_replay`] instead of paying for a browser.
+    async fn capture_deflect_cookie(&self, tab: &headless_chrome::Tab, url: &str) {
+        let Some(host) = url_host(url) else {
+            return;
+        };
+
+        let cookies = match tab.get_cookies() {
+            Ok(cookies) => cookies,
+            Err(e) => {
+                debug!("Failed to read cookies via CDP for {}: {}", url, e);
+                return;
+            }
+        };
+
+        let Some(deflect_cookie) = cookies.into_iter().find(|c| c.name == "deflect") else {
+            return;
+        };
+
+        self.deflect_cookies.lock().await.insert(
+            host,
+            StoredDeflectCookie {
+                value: deflect_cookie.value,
+                captured_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Try to fetch `url` the cheap way: replay a previously-captured
+    /// `deflect` cookie for its host through a plain `reqwest::Client`,
+    /// skipping browser startup entirely. Returns `None` (so the caller
+    /// falls back to [`Self::navigate_and_extract`]) when there's no stored
+    /// cookie, it has expired, or the response still carries the
+    /// verification banner.
+    async fn try_deflect_cookie_replay(&self, url: &str, config: &Config) -> Option<String> {
+        let host = url_host(url)?;
+
+        let cookie_value = {
+            let cookies = self.deflect_cookies.lock().await;
+            let stored = cookies.get(&host)?;
+            if stored.is_expired() {
+                return None;
+            }
+            stored.value.clone()
+        };
+
+        let client = config.http_client().ok()?;
+        let response = client
+            .get(url)
+            .header(
+                reqwest::header::COOKIE,
+                format!("deflect={}", cookie_value),
+            )
+            .header(reqwest::header::USER_AGENT, self.pick_user_agent(config))
+            .send()
+            .await
+            .ok()?;
+        let body = response.text().await.ok()?;
+
+        if is_challenge_banner(&body) {
+            debug!("Stored deflect cookie for {} no longer bypasses the challenge", host);
+            return None;
+        }
+
+        info!("Replayed deflect cookie for {} via reqwest, skipping the browser", host);
+        Some(self.extract_text(&body))
+    }
+
     /// Wait for page to load, handling Deflect challenges automatically
     async fn wait_for_page_load(&self, tab: &headless_chrome::Tab) -> Result<()> {
         // Wait for body element
@@ -164,51 +953,138 @@ impl WebScraper {
         Ok(())
     }
 
-    /// Extract clean text content from HTML
-    fn extract_text(&self, html: &str) -> String {
-        let document = Html::parse_document(html);
-
-        // Remove script and style content
-        let script_selector = Selector::parse("script, style").unwrap();
-        let mut cleaned_html = html.to_string();
+    /// Fetch and cache `url`'s host's `robots.txt`, returning its parsed
+    /// rules. Fetched once per host and reused for every subsequent lookup.
+    async fn robots_rules_for(&self, url: &str) -> Result<RobotsRules> {
+        let host = url_host(url).ok_or_else(|| anyhow!("Invalid URL: {}", url))?;
 
-        for element in document.select(&script_selector) {
-            cleaned_html = cleaned_html.replace(&element.html(), "");
+        {
+            let cache = self.robots_cache.lock().await;
+            if let Some(rules) = cache.get(&host) {
+                return Ok(rules.clone());
+            }
         }
 
-        let clean_doc = Html::parse_document(&cleaned_html);
+        let scheme = url.split_once("://").map(|(s, _)| s).unwrap_or("https");
+        let robots_url = format!("{}://{}/robots.txt", scheme, host);
+
+        let client = Config::from_env().http_client()?;
+        let rules = match client.get(&robots_url).send().await {
+            Ok(response) if response.status().is_success() => response
+                .text()
+                .await
+                .map(|body| parse_robots_txt(&body))
+                .unwrap_or_default(),
+            Ok(_) | Err(_) => {
+                debug!("No usable robots.txt at {}, allowing all paths", robots_url);
+                RobotsRules::default()
+            }
+        };
 
-        // Try content-specific selectors first
-        let content_selectors = ["main", "article", ".content", "#content", ".main"];
+        self.robots_cache
+            .lock()
+            .await
+            .insert(host, rules.clone());
 
-        for selector_str in content_selectors {
-            if let Ok(selector) = Selector::parse(selector_str) {
-                for element in clean_doc.select(&selector) {
-                    let text = element.text().collect::<Vec<_>>().join(" ");
-                    if text.trim().len() > 100 {
-                        return self.clean_text(&text);
+        Ok(rules)
+    }
+
+    /// Follow links breadth-first starting from `root`, scraping each page's
+    /// cleaned text via the same tab pipeline as [`Self::scrape_text`], so
+    /// an entire site section can be ingested into the RAG pipeline from
+    /// one seed URL instead of hand-listing pages. Each depth level is
+    /// scraped with bounded concurrency (the pool's own semaphore caps how
+    /// many tabs run at once), and the traversal stops once `max_pages` is
+    /// reached or the frontier runs dry.
+    pub async fn crawl(&self, root: &str, opts: CrawlOptions) -> Result<Vec<ScrapedPage>> {
+        let root_host =
+            url_host(root).ok_or_else(|| anyhow!("Crawl root is not a valid URL: {}", root))?;
+
+        let mut seen: HashSet<String> = HashSet::new();
+        seen.insert(root.to_string());
+
+        let mut frontier: VecDeque<(String, usize)> = VecDeque::new();
+        frontier.push_back((root.to_string(), 0));
+
+        let mut pages = Vec::new();
+        let max_concurrency = Config::from_env().scraper_max_concurrent.max(1);
+
+        while let Some(&(_, depth)) = frontier.front() {
+            if pages.len() >= opts.max_pages {
+                break;
+            }
+
+            // Pull every URL queued at the current depth so the whole level
+            // can be scraped concurrently.
+            let mut level = Vec::new();
+            while let Some(&(_, d)) = frontier.front() {
+                if d != depth || pages.len() + level.len() >= opts.max_pages {
+                    break;
+                }
+                level.push(frontier.pop_front().unwrap().0);
+            }
+
+            let outcomes: Vec<(String, Result<(String, String)>)> = stream::iter(level)
+                .map(|url| {
+                    let scraper = self.clone();
+                    async move {
+                        let outcome = scraper.navigate_and_extract(&url, &[]).await;
+                        (url, outcome)
                     }
+                })
+                .buffer_unordered(max_concurrency)
+                .collect()
+                .await;
+
+            for (url, outcome) in outcomes {
+                match outcome {
+                    Ok((html, text)) => {
+                        if depth + 1 < opts.max_depth {
+                            for link in self.extract_links(&html, &url) {
+                                if seen.contains(&link) {
+                                    continue;
+                                }
+                                if opts.same_domain_only
+                                    && url_host(&link).as_deref() != Some(root_host.as_str())
+                                {
+                                    continue;
+                                }
+                                seen.insert(link.clone());
+                                frontier.push_back((link, depth + 1));
+                            }
+                        }
+                        pages.push(ScrapedPage { url, text });
+                    }
+                    Err(e) => warn!("Failed to crawl {}: {}", url, e),
+                }
+
+                if pages.len() >= opts.max_pages {
+                    break;
                 }
             }
         }
 
-        // Fallback to full document
-        let all_text: String = clean_doc
-            .root_element()
-            .text()
-            .collect::<Vec<_>>()
-            .join(" ");
+        Ok(pages)
+    }
 
-        self.clean_text(&all_text)
+    /// Collect every `<a href>` on `html` and resolve it to an absolute URL
+    /// against `base_url`, for [`Self::crawl`]'s frontier expansion.
+    fn extract_links(&self, html: &str, base_url: &str) -> Vec<String> {
+        let document = Html::parse_document(html);
+        let Ok(anchor_selector) = Selector::parse("a") else {
+            return Vec::new();
+        };
+
+        document
+            .select(&anchor_selector)
+            .filter_map(|element| element.value().attr("href"))
+            .filter_map(|href| resolve_url(base_url, href))
+            .collect()
     }
 
-    /// Clean and normalize extracted text
-    fn clean_text(&self, text: &str) -> String {
-        text.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty() && line.len() > 2)
-            .collect::<Vec<_>>()
-            .join("\n")
+    /// Extract clean text content from HTML
+    fn extract_text(&self, html: &str) -> String {
+        extract_text_from_html(html)
     }
 }
 
@@ -218,6 +1094,156 @@ pub async fn scrape_url(url: &str) -> Result<String> {
     scraper.scrape_text(url).await
 }
 
+/// Extract clean text content from HTML, preferring a content-specific
+/// selector (`main`, `article`, `.content`, ...) over the whole document
+/// when one holds a substantial amount of text. Shared by [`WebScraper`]
+/// and [`crate::webdriver_scraper::WebDriverScraper`] so both backends
+/// clean up rendered HTML the same way.
+pub(crate) fn extract_text_from_html(html: &str) -> String {
+    let document = Html::parse_document(html);
+
+    // Remove script and style content
+    let script_selector = Selector::parse("script, style").unwrap();
+    let mut cleaned_html = html.to_string();
+
+    for element in document.select(&script_selector) {
+        cleaned_html = cleaned_html.replace(&element.html(), "");
+    }
+
+    let clean_doc = Html::parse_document(&cleaned_html);
+
+    // Try content-specific selectors first
+    let content_selectors = ["main", "article", ".content", "#content", ".main"];
+
+    for selector_str in content_selectors {
+        if let Ok(selector) = Selector::parse(selector_str) {
+            for element in clean_doc.select(&selector) {
+                let text = element.text().collect::<Vec<_>>().join(" ");
+                if text.trim().len() > 100 {
+                    return clean_page_text(&text);
+                }
+            }
+        }
+    }
+
+    // Fallback to full document
+    let all_text: String = clean_doc
+        .root_element()
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    clean_page_text(&all_text)
+}
+
+/// Clean and normalize extracted text: trim each line and drop
+/// blank/near-empty ones.
+fn clean_page_text(text: &str) -> String {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && line.len() > 2)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resolve `href` (absolute, protocol-relative, root-relative, or
+/// path-relative) to an absolute URL against `base`. Returns `None` for
+/// anchors, `javascript:`/`mailto:` links, and other hrefs that aren't
+/// worth following.
+fn resolve_url(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    if href.starts_with("javascript:") || href.starts_with("mailto:") || href.starts_with("tel:") {
+        return None;
+    }
+    if href.starts_with("http://") || href.starts_with("https://") {
+        return Some(href.to_string());
+    }
+
+    let (scheme, rest) = base.split_once("://")?;
+    if let Some(host_and_path) = href.strip_prefix("//") {
+        return Some(format!("{}://{}", scheme, host_and_path));
+    }
+
+    let host_end = rest.find('/').unwrap_or(rest.len());
+    let host = &rest[..host_end];
+
+    if let Some(path) = href.strip_prefix('/') {
+        return Some(format!("{}://{}/{}", scheme, host, path));
+    }
+
+    // Relative to the current page's directory.
+    let base_path = &rest[host_end..];
+    let dir_end = base_path.rfind('/').map(|i| i + 1).unwrap_or(0);
+    Some(format!(
+        "{}://{}{}{}",
+        scheme,
+        host,
+        &base_path[..dir_end],
+        href
+    ))
+}
+
+/// Parse a `robots.txt` body into the rules that apply to the
+/// `User-agent: *` group; named user-agent groups are ignored since the
+/// scraper doesn't identify itself under a specific bot name.
+fn parse_robots_txt(body: &str) -> RobotsRules {
+    let mut rules = RobotsRules::default();
+    let mut in_wildcard_group = false;
+
+    for line in body.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let field = field.trim().to_lowercase();
+        let value = value.trim();
+
+        match field.as_str() {
+            "user-agent" => in_wildcard_group = value == "*",
+            "disallow" if in_wildcard_group && !value.is_empty() => {
+                rules.disallow.push(value.to_string());
+            }
+            "crawl-delay" if in_wildcard_group => {
+                if let Ok(secs) = value.parse::<f64>() {
+                    rules.crawl_delay = Some(Duration::from_secs_f64(secs));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    rules
+}
+
+/// Extract the path (plus query string) component of a URL, falling back
+/// to `/` when there is none, for matching against robots.txt `Disallow` prefixes.
+fn url_path(url: &str) -> String {
+    match url.split_once("://") {
+        Some((_, rest)) => match rest.find('/') {
+            Some(idx) => rest[idx..].to_string(),
+            None => "/".to_string(),
+        },
+        None => "/".to_string(),
+    }
+}
+
+/// Extract the lowercased host component from a URL, used by
+/// [`WebScraper::crawl`]'s `same_domain_only` scoping.
+fn url_host(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = host.split('@').next_back()?;
+    let host = host.split(':').next()?;
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_lowercase())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +1254,22 @@ mod tests {
         assert!(scraper.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_tab_pool_bounds_concurrency() {
+        let scraper = WebScraper::new().await.unwrap();
+        let config = Config::from_env();
+
+        // The pool has one browser slot per `scraper_pool_size`, and the
+        // semaphore admits at most `scraper_max_concurrent` scrapes at once
+        // regardless of which entry point (scrape_text, the deflect cookie
+        // fallback, crawl) acquires it.
+        assert_eq!(scraper.browsers.len(), config.scraper_pool_size.max(1));
+        assert_eq!(
+            scraper.permits.available_permits(),
+            config.scraper_max_concurrent.max(1)
+        );
+    }
+
     #[tokio::test]
     async fn test_concurrent_scraping() {
         let scraper = WebScraper::new().await.unwrap();
@@ -253,4 +1295,189 @@ mod tests {
             assert!(!text.is_empty());
         }
     }
+
+    /// A deliberately minimal single-purpose HTTP/1.1 server: it serves
+    /// exactly the canned `path -> body` responses it's given, over plain
+    /// TCP, just enough to drive [`WebScraper::scrape_text`] against
+    /// `127.0.0.1` instead of a live site. Every connection is handled on its
+    /// own thread so a slow browser read (e.g. while a canned page's JS is
+    /// still "solving" the fake challenge) can't stall other requests.
+    struct DumbServer {
+        addr: std::net::SocketAddr,
+    }
+
+    impl DumbServer {
+        fn start(routes: HashMap<&'static str, &'static str>) -> Self {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0")
+                .expect("failed to bind dumb test server");
+            let addr = listener.local_addr().expect("failed to read local addr");
+
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+                    let routes = routes.clone();
+                    std::thread::spawn(move || {
+                        use std::io::{Read, Write};
+
+                        let mut buf = [0u8; 1024];
+                        let _ = stream.read(&mut buf);
+                        let request = String::from_utf8_lossy(&buf);
+                        let path = request
+                            .lines()
+                            .next()
+                            .and_then(|line| line.split_whitespace().nth(1))
+                            .unwrap_or("/");
+                        let body = routes
+                            .get(path)
+                            .copied()
+                            .unwrap_or("<html><body>not found</body></html>");
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        let _ = stream.write_all(response.as_bytes());
+                    });
+                }
+            });
+
+            Self { addr }
+        }
+
+        fn url(&self, path: &str) -> String {
+            format!("http://{}{}", self.addr, path)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrape_text_extracts_from_dumb_server() {
+        let mut routes = HashMap::new();
+        routes.insert(
+            "/",
+            "<html><body><main><h1>Dumb Server Page</h1><p>Deterministic content for the extraction test.</p></main></body></html>",
+        );
+        let server = DumbServer::start(routes);
+
+        let scraper = WebScraper::new().await.unwrap();
+        let text = scraper.scrape_text(&server.url("/")).await.unwrap();
+
+        assert!(text.contains("Dumb Server Page"));
+        assert!(text.contains("Deterministic content"));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_text_resolves_fake_deflect_challenge() {
+        let mut routes = HashMap::new();
+        routes.insert(
+            "/",
+            r#"<html><head><title>Verifying you are human</title></head>
+            <body>
+                <div>Please wait while we check your browser (deflect challenge)...</div>
+                <script>
+                    setTimeout(function () {
+                        document.cookie = "deflect=fake-token; path=/";
+                        document.body.innerHTML =
+                            "<main><h1>Challenge Cleared</h1><p>You made it through the check.</p></main>";
+                    }, 500);
+                </script>
+            </body></html>"#,
+        );
+        let server = DumbServer::start(routes);
+
+        let scraper = WebScraper::new().await.unwrap();
+        let text = scraper.scrape_text(&server.url("/")).await.unwrap();
+
+        assert!(text.contains("Challenge Cleared"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_interception_fulfills_stubbed_response() {
+        use headless_chrome::protocol::cdp::Fetch::FulfillRequest;
+
+        let scraper = WebScraper::new().await.unwrap();
+        let tab = scraper.browsers[0].lock().await.new_tab().unwrap();
+
+        let patterns = vec![RequestPattern {
+            url_pattern: Some("*stubbed*".to_string()),
+            resource_type: None,
+            request_stage: Some(RequestStage::Request),
+        }];
+
+        tab.enable_request_interception(
+            &patterns,
+            Arc::new(|_transport, _session_id, event: RequestPausedEvent| {
+                if event.params.request.url.contains("stubbed") {
+                    RequestPausedDecision::Fulfill(FulfillRequest {
+                        request_id: event.params.request_id,
+                        response_code: 200,
+                        response_headers: None,
+                        binary_response_headers: None,
+                        body: Some(
+                            "<html><body><main><h1>Stubbed</h1><p>Fulfilled without touching the network.</p></main></body></html>"
+                                .to_string(),
+                        ),
+                        response_phrase: None,
+                    })
+                } else {
+                    RequestPausedDecision::Continue(None)
+                }
+            }),
+        )
+        .unwrap();
+
+        tab.navigate_to("http://127.0.0.1:1/stubbed-page").unwrap();
+        let _ = tab.wait_for_element("body");
+        let html = tab.get_content().unwrap();
+
+        assert!(html.contains("Stubbed"));
+    }
+
+    #[test]
+    fn test_resolve_url_variants() {
+        let base = "https://example.com/blog/post";
+        assert_eq!(
+            resolve_url(base, "https://other.com/page"),
+            Some("https://other.com/page".to_string())
+        );
+        assert_eq!(
+            resolve_url(base, "//cdn.example.com/asset"),
+            Some("https://cdn.example.com/asset".to_string())
+        );
+        assert_eq!(
+            resolve_url(base, "/about"),
+            Some("https://example.com/about".to_string())
+        );
+        assert_eq!(
+            resolve_url(base, "more"),
+            Some("https://example.com/blog/more".to_string())
+        );
+        assert_eq!(resolve_url(base, "#section"), None);
+        assert_eq!(resolve_url(base, "mailto:hi@example.com"), None);
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(
+            url_host("https://example.com/page?x=1"),
+            Some("example.com".to_string())
+        );
+        assert_eq!(url_host("not a url"), None);
+    }
+
+    #[test]
+    fn test_parse_robots_txt_wildcard_group_only() {
+        let body = "User-agent: Googlebot\nDisallow: /googlebot-only\n\nUser-agent: *\nDisallow: /private\nCrawl-delay: 2\n";
+        let rules = parse_robots_txt(body);
+
+        assert!(rules.is_allowed("/public"));
+        assert!(!rules.is_allowed("/private/secret"));
+        assert!(rules.is_allowed("/googlebot-only"));
+        assert_eq!(rules.crawl_delay, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_url_path() {
+        assert_eq!(url_path("https://example.com/a/b?x=1"), "/a/b?x=1");
+        assert_eq!(url_path("https://example.com"), "/");
+    }
 }
@@ -0,0 +1,31 @@
+use rand::Rng;
+
+use crate::config::Config;
+
+/// Realistic desktop and mobile User-Agent strings used when `Config` has no
+/// override pool configured. A request carrying the same fixed UA on every
+/// call is itself a fingerprinting signal, so engine/page fetches rotate
+/// through this list instead.
+const DEFAULT_USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15",
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:125.0) Gecko/20100101 Firefox/125.0",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
+];
+
+/// Picks a User-Agent string for an outbound engine/scrape request: one of
+/// `Config::user_agents` when non-empty, otherwise one of
+/// [`DEFAULT_USER_AGENTS`]. Selection is just an index into a static slice,
+/// so it's cheap enough to call on every request through the shared
+/// `WebScraper` singleton.
+pub fn random_user_agent(config: &Config) -> String {
+    if config.user_agents.is_empty() {
+        let idx = rand::thread_rng().gen_range(0..DEFAULT_USER_AGENTS.len());
+        DEFAULT_USER_AGENTS[idx].to_string()
+    } else {
+        let idx = rand::thread_rng().gen_range(0..config.user_agents.len());
+        config.user_agents[idx].clone()
+    }
+}
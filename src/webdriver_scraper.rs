@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::scraper::{Interaction, ScraperBackend, extract_text_from_html};
+
+/// [`ScraperBackend`] that drives a page through an external WebDriver
+/// endpoint (geckodriver, chromedriver, Selenium Grid, ...) via `fantoccini`,
+/// for sites that need a real interactive session beyond what the CDP-driven
+/// [`crate::scraper::WebScraper`] exposes - e.g. environments where only a
+/// WebDriver endpoint is reachable, or pages already scripted against the
+/// WebDriver protocol.
+pub struct WebDriverScraper {
+    client: fantoccini::Client,
+}
+
+impl WebDriverScraper {
+    /// Connect to a running WebDriver server at `webdriver_url`
+    /// (e.g. `http://localhost:4444`).
+    pub async fn connect(webdriver_url: &str) -> Result<Self> {
+        let client = fantoccini::ClientBuilder::native()
+            .connect(webdriver_url)
+            .await
+            .with_context(|| format!("Failed to connect to WebDriver at {}", webdriver_url))?;
+        Ok(Self { client })
+    }
+
+    /// Navigate to `url`, run `steps` against the page, then extract and
+    /// clean its rendered HTML the same way [`crate::scraper::WebScraper`] does.
+    pub async fn scrape_text_with_interactions(
+        &self,
+        url: &str,
+        steps: &[Interaction],
+    ) -> Result<String> {
+        self.client
+            .goto(url)
+            .await
+            .with_context(|| format!("Failed to navigate to {}", url))?;
+
+        self.run_interactions(steps).await?;
+
+        let html = self.source().await?;
+        Ok(extract_text_from_html(&html))
+    }
+
+    /// Scrape a URL with no pre-extraction interactions.
+    pub async fn scrape_text(&self, url: &str) -> Result<String> {
+        self.scrape_text_with_interactions(url, &[]).await
+    }
+
+    /// Close the underlying WebDriver session.
+    pub async fn close(self) -> Result<()> {
+        self.client
+            .close()
+            .await
+            .context("Failed to close WebDriver session")
+    }
+}
+
+#[async_trait]
+impl ScraperBackend for WebDriverScraper {
+    async fn click(&self, selector: &str) -> Result<()> {
+        let element = self
+            .client
+            .find(fantoccini::Locator::Css(selector))
+            .await
+            .with_context(|| format!("Element not found for click: {}", selector))?;
+        element
+            .click()
+            .await
+            .with_context(|| format!("Failed to click: {}", selector))?;
+        Ok(())
+    }
+
+    async fn fill_form(&self, fields: &HashMap<String, String>) -> Result<()> {
+        for (selector, value) in fields {
+            let mut element = self
+                .client
+                .find(fantoccini::Locator::Css(selector))
+                .await
+                .with_context(|| format!("Form field not found: {}", selector))?;
+            element
+                .send_keys(value)
+                .await
+                .with_context(|| format!("Failed to fill field: {}", selector))?;
+        }
+        Ok(())
+    }
+
+    async fn submit(&self) -> Result<()> {
+        // fantoccini has no generic "submit the active form" call; drive it
+        // through whichever element was last focused, same as the headless
+        // Chrome backend.
+        self.client
+            .execute(
+                "document.activeElement && document.activeElement.form && document.activeElement.form.submit();",
+                vec![],
+            )
+            .await
+            .context("Failed to submit form")?;
+        Ok(())
+    }
+
+    async fn source(&self) -> Result<String> {
+        self.client
+            .source()
+            .await
+            .context("Failed to read page source")
+    }
+
+    async fn wait_for_element(&self, selector: &str) -> Result<()> {
+        self.client
+            .wait()
+            .for_element(fantoccini::Locator::Css(selector))
+            .await
+            .with_context(|| format!("Element never appeared: {}", selector))?;
+        Ok(())
+    }
+
+    async fn focus(&self, selector: &str) -> Result<()> {
+        // fantoccini has no generic "focus" call; drive it through the same
+        // DOM API the headless Chrome backend's `type_into`/`click` rely on.
+        let script = format!("document.querySelector({:?})?.focus()", selector);
+        self.client
+            .execute(&script, vec![])
+            .await
+            .with_context(|| format!("Failed to focus: {}", selector))?;
+        Ok(())
+    }
+
+    async fn type_text(&self, selector: &str, text: &str) -> Result<()> {
+        let mut element = self
+            .client
+            .find(fantoccini::Locator::Css(selector))
+            .await
+            .with_context(|| format!("Element not found: {}", selector))?;
+        element
+            .send_keys(text)
+            .await
+            .with_context(|| format!("Failed to type into: {}", selector))?;
+        Ok(())
+    }
+
+    async fn scroll_to(&self, selector: &str) -> Result<()> {
+        let script = format!(
+            "document.querySelector({:?})?.scrollIntoView({{behavior: 'instant', block: 'center'}})",
+            selector
+        );
+        self.client
+            .execute(&script, vec![])
+            .await
+            .with_context(|| format!("Failed to scroll to: {}", selector))?;
+        Ok(())
+    }
+}